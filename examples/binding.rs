@@ -0,0 +1,82 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::observable::{BindingMode, Observable};
+use pushrod::render::resources::DEFAULT_FONT;
+use pushrod::render::widget_config::{KEY_SELECTED_STATE, KEY_TEXT};
+use pushrod::widgets::checkbox_widget::CheckboxWidget;
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+use pushrod::widgets::toggle_button_widget::ToggleButtonWidget;
+
+/*
+ * This demo binds a single `Observable` to a toggle button and a checkbox.  Changing either one
+ * changes the other, and the status text follows along, without any callback touching a `Widget`.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render binding demo", 400, 260)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let enabled = Observable::new(false);
+    let status = Observable::new(String::from("Feature is off"));
+
+    engine.setup(400, 260);
+
+    let status_model = status.clone();
+
+    enabled.subscribe(move |on| {
+        status_model.set(String::from(if *on {
+            "Feature is on"
+        } else {
+            "Feature is off"
+        }));
+    });
+
+    let toggle = ToggleButtonWidget::new(20, 20, 360, 60, String::from("Feature"), 32, false);
+    let toggle_id = engine.add_widget(Box::new(toggle), String::from("toggle"));
+    let checkbox = CheckboxWidget::new(20, 100, 360, 40, String::from("Feature"), 24, false);
+    let checkbox_id = engine.add_widget(Box::new(checkbox), String::from("checkbox"));
+    let text = TextWidget::new(
+        String::from(DEFAULT_FONT),
+        sdl2::ttf::FontStyle::NORMAL,
+        24,
+        TextJustify::Center,
+        String::new(),
+        20,
+        150,
+        360,
+        30,
+    );
+    let text_id = engine.add_widget(Box::new(text), String::from("status"));
+    let mut reset = PushButtonWidget::new(20, 190, 360, 50, String::from("Reset"), 28);
+    let reset_model = enabled.clone();
+
+    reset.on_click(move |_x, _widgets| reset_model.set(false));
+
+    engine.add_widget(Box::new(reset), String::from("reset"));
+
+    engine
+        .bind(toggle_id, KEY_SELECTED_STATE, &enabled, BindingMode::TwoWay)
+        .unwrap();
+    engine
+        .bind(
+            checkbox_id,
+            KEY_SELECTED_STATE,
+            &enabled,
+            BindingMode::TwoWay,
+        )
+        .unwrap();
+    engine
+        .bind(text_id, KEY_TEXT, &status, BindingMode::OneWay)
+        .unwrap();
+
+    engine.run(sdl_context, window);
+}
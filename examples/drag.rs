@@ -0,0 +1,60 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, KEY_ORIGIN};
+use sdl2::pixels::Color;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/*
+ * This demo tests mouse capture, by moving a panel around the window as it is dragged.  The
+ * panel keeps following the mouse, even when the mouse moves outside of the panel's bounds.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render drag demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let mut panel = BaseWidget::new(20, 20, 100, 80);
+    let last_point = Rc::new(RefCell::new(vec![0, 0]));
+    let drag_point = last_point.clone();
+
+    panel.set_color(CONFIG_COLOR_BASE, Color::RGB(192, 192, 255));
+    panel.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    panel
+        .get_callbacks()
+        .on_drag_start(move |_x, _widgets, points| {
+            *last_point.borrow_mut() = points;
+        });
+    panel.get_callbacks().on_drag(move |x, _widgets, points| {
+        let origin = x.get_config().try_get(KEY_ORIGIN).unwrap_or_default();
+        let mut last = drag_point.borrow_mut();
+
+        x.set_origin(vec![
+            origin[0] + points[0] - last[0],
+            origin[1] + points[1] - last[1],
+        ]);
+        *last = points;
+
+        // The base widget is redrawn to erase the panel's previous position.
+        _widgets[0]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .set_invalidate(true);
+    });
+
+    engine.setup(400, 300);
+
+    engine.add_widget(Box::new(panel), String::from("panel"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::callbacks::CallbackRegistry;
+use pushrod::render::canvas::RenderCanvas;
+use pushrod::render::drag_drop::DragPayload;
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    WidgetConfig, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+};
+use sdl2::pixels::Color;
+use std::collections::HashMap;
+
+/*
+ * This demo tests drag and drop.  Drag the blue box onto the drop target to drop its text, or
+ * drop a file from the desktop onto the drop target.  The drop target is highlighted while an
+ * accepted payload is dragged over it.
+ */
+
+struct DragSourceWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    text: String,
+}
+
+impl DragSourceWidget {
+    fn new(x: i32, y: i32, w: u32, h: u32, text: String) -> Self {
+        Self {
+            config: WidgetConfig::new(x, y, w, h),
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            text,
+        }
+    }
+}
+
+impl Widget for DragSourceWidget {
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        c.set_draw_color(Color::RGB(128, 128, 255));
+        c.fill_rect(self.get_drawing_area()).unwrap();
+    }
+
+    fn drag_payload(&mut self) -> Option<DragPayload> {
+        Some(DragPayload::Text(self.text.clone()))
+    }
+
+    default_widget_properties!();
+}
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render drag and drop demo", 400, 200)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let source = DragSourceWidget::new(20, 20, 100, 100, String::from("Hello from the blue box"));
+    let mut target = BaseWidget::new(200, 20, 180, 160);
+
+    target.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+    target.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    target.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    target.get_callbacks().on_drop_entered(|x, _widgets| {
+        x.set_color(CONFIG_COLOR_BASE, Color::RGB(192, 255, 192));
+        x.get_config().set_invalidate(true);
+    });
+    target.get_callbacks().on_drop_exited(|x, _widgets| {
+        x.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 255));
+        x.get_config().set_invalidate(true);
+    });
+    target
+        .get_callbacks()
+        .on_drop(|_x, _widgets, payload, points| match payload {
+            DragPayload::Text(text) => eprintln!("Dropped text at {:?}: {}", points, text),
+            DragPayload::File(path) => eprintln!("Dropped file at {:?}: {}", points, path),
+            DragPayload::Custom(_) => eprintln!("Dropped custom payload at {:?}", points),
+        });
+
+    engine.setup(400, 200);
+
+    engine.add_widget(Box::new(source), String::from("source"));
+    engine.add_widget(Box::new(target), String::from("target"));
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,70 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::event::Event;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use std::time::Duration;
+
+/*
+ * This demo runs the `Engine` as an overlay inside of an application that drives its own event
+ * loop and canvas.  The application animates a box behind the `Widget`s on every frame.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render embedded demo", 400, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut canvas = window.into_canvas().software().build().unwrap();
+    let mut event_pump = sdl_context.event_pump().unwrap();
+    let mut engine = Engine::new();
+    let mut button1 = PushButtonWidget::new(20, 220, 360, 60, String::from("Overlay"), 40);
+
+    button1.on_click(|_x, _widgets| {
+        eprintln!("Overlay button clicked!");
+    });
+
+    engine.setup(400, 300);
+
+    if let Some(base) = engine.get_widget_cache().get_container_by_id(0) {
+        base.widget.borrow_mut().get_config().hide();
+    }
+
+    engine.add_widget(Box::new(button1), String::from("button1"));
+
+    let mut box_x = 0;
+
+    while engine.is_running() {
+        for event in event_pump.poll_iter() {
+            if let Event::Quit { .. } = event {
+                return;
+            }
+
+            engine.handle_event(&event);
+        }
+
+        engine.tick();
+
+        box_x = (box_x + 2) % 400;
+
+        canvas.set_draw_color(Color::RGB(0, 0, 64));
+        canvas.clear();
+        canvas.set_draw_color(Color::RGB(255, 255, 0));
+        canvas.fill_rect(Rect::new(box_x, 80, 40, 40)).unwrap();
+
+        // The frame was cleared, so all of the widgets are drawn again, not only those that
+        // changed.
+        engine.get_widget_cache().invalidate_all();
+        engine.draw(&mut canvas);
+        canvas.present();
+
+        ::std::thread::sleep(Duration::new(0, 1_000_000_000u32 / 60));
+    }
+}
@@ -0,0 +1,33 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{CONFIG_COLOR_SECONDARY, CONFIG_PROGRESS};
+use pushrod::widgets::progress_widget::ProgressWidget;
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::pixels::Color;
+
+/*
+ * This demo renders a set of `Widget`s to an offscreen surface without opening a window, and
+ * saves the result as a bitmap.  No display is required to run this demo.
+ */
+
+pub fn main() {
+    let mut engine = Engine::new();
+    let button1 = PushButtonWidget::new(20, 20, 360, 60, String::from("Offscreen"), 40);
+    let mut progress1 = ProgressWidget::new(20, 100, 360, 40);
+
+    progress1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+    progress1.set_numeric(CONFIG_PROGRESS, 50);
+
+    engine.setup(400, 160);
+
+    engine.add_widget(Box::new(button1), String::from("button1"));
+    engine.add_widget(Box::new(progress1), String::from("progress1"));
+
+    let surface = engine.render_to_surface(400, 160).unwrap();
+
+    surface.save_bmp("headless.bmp").unwrap();
+    eprintln!("Rendered to headless.bmp");
+}
@@ -0,0 +1,93 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::resources::DEFAULT_FONT;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{
+    CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, KEY_ORIGIN,
+};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::*;
+use sdl2::pixels::Color;
+use sdl2::ttf::FontStyle;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/*
+ * This demo tests nested `Widget`s.  The panel contains a label and a button, which are positioned
+ * relative to the panel, and move with it as it is dragged.  The button extends past the right
+ * edge of the panel, and is clipped to it.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render nested widgets demo", 500, 300)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let mut panel = BaseWidget::new(20, 20, 240, 140);
+    let last_point = Rc::new(RefCell::new(vec![0, 0]));
+    let drag_point = last_point.clone();
+
+    panel.set_color(CONFIG_COLOR_BASE, Color::RGB(224, 224, 224));
+    panel.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    panel.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    panel
+        .get_callbacks()
+        .on_drag_start(move |_x, _widgets, points| {
+            *last_point.borrow_mut() = points;
+        });
+    panel.get_callbacks().on_drag(move |x, _widgets, points| {
+        let origin = x.get_config().try_get(KEY_ORIGIN).unwrap_or_default();
+        let mut last = drag_point.borrow_mut();
+
+        x.set_origin(vec![
+            origin[0] + points[0] - last[0],
+            origin[1] + points[1] - last[1],
+        ]);
+        *last = points;
+
+        // The base widget is redrawn to erase the panel's previous position.
+        _widgets[0]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .set_invalidate(true);
+    });
+
+    let label = TextWidget::new(
+        String::from(DEFAULT_FONT),
+        FontStyle::NORMAL,
+        16,
+        TextJustify::Left,
+        String::from("Drag this panel"),
+        10,
+        10,
+        220,
+        24,
+    );
+
+    let mut button = PushButtonWidget::new(10, 60, 300, 60, String::from("Clipped"), 32);
+
+    button.on_click(|_x, _widgets| {
+        eprintln!("Nested button clicked!");
+    });
+
+    engine.setup(500, 300);
+
+    let panel_id = engine.add_widget(Box::new(panel), String::from("panel"));
+
+    engine
+        .add_child(panel_id, Box::new(label), String::from("label"))
+        .unwrap();
+    engine
+        .add_child(panel_id, Box::new(button), String::from("button"))
+        .unwrap();
+
+    engine.run(sdl_context, window);
+}
@@ -0,0 +1,87 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::pixels::Color;
+
+/*
+ * This demo tests the stacking order of `Widget`s.  Clicking on a card brings it to the front.
+ * The popup is added to the overlay layer before any of the cards, but is still drawn above them.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render overlay demo", 400, 360)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let mut popup = BaseWidget::new(200, 40, 180, 120);
+
+    popup.set_color(CONFIG_COLOR_BASE, Color::RGB(255, 255, 192));
+    popup.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    popup.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    popup.get_config().hide();
+
+    engine.setup(400, 360);
+
+    let popup_id = engine.add_overlay(Box::new(popup), String::from("popup"));
+    let colors = [
+        Color::RGB(255, 128, 128),
+        Color::RGB(128, 255, 128),
+        Color::RGB(128, 128, 255),
+    ];
+
+    for (i, color) in colors.iter().enumerate() {
+        let name = format!("card{}", i + 1);
+        let card_name = name.clone();
+        let handle = engine.get_handle();
+        let offset = i as i32 * 50;
+        let mut card = BaseWidget::new(20 + offset, 20 + offset, 200, 150);
+
+        card.set_color(CONFIG_COLOR_BASE, *color);
+        card.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+        card.set_numeric(CONFIG_BORDER_WIDTH, 2);
+        card.get_callbacks()
+            .on_mouse_clicked(move |_x, _widgets, _button, _clicks, state| {
+                if !state {
+                    return;
+                }
+
+                if let Some(container) = _widgets.iter().find(|x| x.get_widget_name() == card_name)
+                {
+                    handle.bring_to_front(container.get_id());
+                }
+            });
+
+        engine.add_widget(Box::new(card), name);
+    }
+
+    let handle = engine.get_handle();
+    let mut toggle_popup =
+        PushButtonWidget::new(20, 280, 360, 60, String::from("Toggle popup"), 32);
+
+    toggle_popup.on_click(move |_x, _widgets| {
+        if let Some(container) = _widgets.iter().find(|x| x.get_id() == popup_id) {
+            let mut widget = container.widget.borrow_mut();
+
+            if widget.get_config().is_hidden() {
+                widget.get_config().show();
+            } else {
+                widget.get_config().hide();
+            }
+        }
+
+        handle.request_repaint();
+    });
+
+    engine.add_widget(Box::new(toggle_popup), String::from("toggle_popup"));
+
+    engine.run(sdl_context, window);
+}
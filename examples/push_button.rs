@@ -16,7 +16,7 @@ pub fn main() {
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
     let window = video_subsystem
-        .window("pushrod-render push button demo", 400, 100)
+        .window("pushrod-render push button demo", 400, 180)
         .position_centered()
         .opengl()
         .build()
@@ -30,9 +30,24 @@ pub fn main() {
         eprintln!("Click me clicked!");
     });
 
-    engine.setup(400, 100);
+    let handle = engine.get_handle();
+    let mut quit_button = PushButtonWidget::new(20, 100, 360, 60, String::from("Quit"), 40);
+
+    quit_button.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    quit_button.set_numeric(CONFIG_BORDER_WIDTH, 2);
+    quit_button.on_click(move |_x, _widgets| {
+        handle.quit();
+    });
+
+    engine.on_quit_requested(|_widgets| {
+        eprintln!("Quit requested.");
+        true
+    });
+
+    engine.setup(400, 180);
 
     engine.add_widget(Box::new(button1), String::from("button1"));
+    engine.add_widget(Box::new(quit_button), String::from("quit_button"));
 
     engine.run(sdl_context, window);
 }
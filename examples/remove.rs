@@ -0,0 +1,62 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::widget::{BaseWidget, Widget};
+use pushrod::render::widget_config::{CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER};
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use sdl2::pixels::Color;
+
+/*
+ * This demo tests removing `Widget`s at runtime.  Each button in the panel removes itself, and
+ * the button below the panel removes the panel, along with any buttons that are left in it.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render widget removal demo", 400, 260)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let mut panel = BaseWidget::new(20, 20, 360, 140);
+
+    panel.set_color(CONFIG_COLOR_BASE, Color::RGB(224, 224, 224));
+    panel.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+    panel.set_numeric(CONFIG_BORDER_WIDTH, 2);
+
+    engine.setup(400, 260);
+
+    let panel_id = engine.add_widget(Box::new(panel), String::from("panel"));
+
+    for i in 0..2 {
+        let name = format!("button{}", i + 1);
+        let button_name = name.clone();
+        let handle = engine.get_handle();
+        let mut button =
+            PushButtonWidget::new(10, 10 + i * 65, 340, 55, String::from("Remove me"), 28);
+
+        button.on_click(move |_x, _widgets| {
+            if let Some(container) = _widgets.iter().find(|x| x.get_widget_name() == button_name) {
+                handle.remove_widget(container.get_id());
+            }
+        });
+
+        engine.add_child(panel_id, Box::new(button), name).unwrap();
+    }
+
+    let handle = engine.get_handle();
+    let mut remove_panel =
+        PushButtonWidget::new(20, 180, 360, 60, String::from("Remove panel"), 32);
+
+    remove_panel.on_click(move |_x, _widgets| {
+        handle.remove_widget(panel_id);
+    });
+
+    engine.add_widget(Box::new(remove_panel), String::from("remove_panel"));
+
+    engine.run(sdl_context, window);
+}
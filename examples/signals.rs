@@ -0,0 +1,66 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::resources::DEFAULT_FONT;
+use pushrod::render::signals::Signal;
+use pushrod::render::widget::TypedConfig;
+use pushrod::render::widget_config::KEY_TEXT;
+use pushrod::widgets::push_button_widget::PushButtonWidget;
+use pushrod::widgets::text_widget::{TextJustify, TextWidget};
+
+/*
+ * This demo uses a signal to connect a button to a label and to the window title.  The button
+ * does not know what reacts to it, and neither reaction needs to look the button up.
+ */
+
+const COUNT_CHANGED: Signal<i32> = Signal::new("signals_demo::count_changed");
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render signals demo", 400, 180)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+
+    engine.setup(400, 180);
+
+    let label = TextWidget::new(
+        String::from(DEFAULT_FONT),
+        sdl2::ttf::FontStyle::NORMAL,
+        28,
+        TextJustify::Center,
+        String::from("Clicked 0 times"),
+        20,
+        20,
+        360,
+        40,
+    );
+    let label_id = engine.add_widget(Box::new(label), String::from("label"));
+    let mut button = PushButtonWidget::new(20, 100, 360, 60, String::from("Click me"), 32);
+    let handle = engine.get_handle();
+    let mut count = 0;
+
+    button.on_click(move |_x, _widgets| {
+        count += 1;
+        handle.emit(COUNT_CHANGED, count);
+    });
+
+    engine.add_widget(Box::new(button), String::from("button"));
+
+    engine.connect_widget(label_id, COUNT_CHANGED, |widget, count, _widgets| {
+        widget.set_value(KEY_TEXT, format!("Clicked {} times", count));
+    });
+
+    let handle = engine.get_handle();
+
+    engine.connect(COUNT_CHANGED, move |count, _widgets| {
+        handle.set_window_title(format!("pushrod-render signals demo ({})", count));
+    });
+
+    engine.run(sdl_context, window);
+}
@@ -2,6 +2,7 @@ extern crate pushrod;
 extern crate sdl2;
 
 use pushrod::render::engine::Engine;
+use pushrod::render::resources::{FontFamily, DEFAULT_FONT};
 use pushrod::render::widget::Widget;
 use pushrod::render::widget_config::CONFIG_COLOR_TEXT;
 use pushrod::widgets::text_widget::*;
@@ -18,8 +19,8 @@ pub fn main() {
         .unwrap();
     let mut engine = Engine::new();
     let mut widget1 = TextWidget::new(
-        String::from("assets/OpenSans-Regular.ttf"),
-        sdl2::ttf::FontStyle::NORMAL,
+        String::from("title"),
+        sdl2::ttf::FontStyle::BOLD,
         28,
         TextJustify::Left,
         String::from("Left Justified"),
@@ -34,7 +35,7 @@ pub fn main() {
         .set_color(CONFIG_COLOR_TEXT, Color::RGB(255, 0, 0));
 
     let mut widget2 = TextWidget::new(
-        String::from("assets/OpenSans-Regular.ttf"),
+        String::from(DEFAULT_FONT),
         sdl2::ttf::FontStyle::NORMAL,
         28,
         TextJustify::Center,
@@ -50,7 +51,7 @@ pub fn main() {
         .set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 255, 0));
 
     let mut widget3 = TextWidget::new(
-        String::from("assets/OpenSans-Regular.ttf"),
+        String::from(DEFAULT_FONT),
         sdl2::ttf::FontStyle::NORMAL,
         28,
         TextJustify::Right,
@@ -67,6 +68,12 @@ pub fn main() {
 
     engine.setup(500, 200);
 
+    // Registers a font under a name, so that `Widget`s can refer to it by name instead of path.
+    engine.get_resources().register_font(
+        String::from("title"),
+        FontFamily::new(String::from("assets/OpenSans-Regular.ttf")),
+    );
+
     engine.add_widget(Box::new(widget1), String::from("widget1"));
     engine.add_widget(Box::new(widget2), String::from("widget2"));
     engine.add_widget(Box::new(widget3), String::from("widget3"));
@@ -0,0 +1,63 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::resources::DEFAULT_FONT;
+use pushrod::widgets::text_box_widget::TextBoxWidget;
+
+/*
+ * This demo tests text entry using the `TextBoxWidget`.  Click on a text box to give it focus,
+ * then type.  Pressing Enter submits the text.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render text box demo", 400, 140)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let mut text_box1 = TextBoxWidget::new(
+        String::from(DEFAULT_FONT),
+        24,
+        String::from("Type here"),
+        20,
+        20,
+        360,
+        40,
+    );
+
+    text_box1.on_text_changed(|_x, _widgets, text| {
+        eprintln!("Text changed: {}", text);
+    });
+
+    text_box1.on_submit(|_x, _widgets, text| {
+        eprintln!("Text submitted: {}", text);
+    });
+
+    let mut text_box2 = TextBoxWidget::new(
+        String::from(DEFAULT_FONT),
+        24,
+        String::from(""),
+        20,
+        80,
+        360,
+        40,
+    );
+
+    text_box2.on_submit(|_x, _widgets, text| {
+        eprintln!("Second text submitted: {}", text);
+    });
+
+    engine.setup(400, 140);
+
+    let text_box1_id = engine.add_widget(Box::new(text_box1), String::from("text_box1"));
+    engine.add_widget(Box::new(text_box2), String::from("text_box2"));
+    engine.set_focus(text_box1_id);
+    engine.set_idle_mode(true);
+
+    engine.run(sdl_context, window);
+}
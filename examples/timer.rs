@@ -1,11 +1,12 @@
 extern crate pushrod;
 extern crate sdl2;
 
-use pushrod::render::callbacks::widget_id_for_name;
+use pushrod::render::callbacks::{widget_id_for_name, with_widget};
 use pushrod::render::engine::Engine;
-use pushrod::render::widget::Widget;
-use pushrod::render::widget_config::{CONFIG_COLOR_SECONDARY, CONFIG_PROGRESS};
+use pushrod::render::widget::{TypedConfig, Widget};
+use pushrod::render::widget_config::{CONFIG_COLOR_SECONDARY, CONFIG_PROGRESS, KEY_PROGRESS};
 use pushrod::widgets::progress_widget::*;
+use pushrod::widgets::push_button_widget::PushButtonWidget;
 use pushrod::widgets::timer_widget::*;
 use sdl2::pixels::Color;
 
@@ -13,7 +14,7 @@ pub fn main() {
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
     let window = video_subsystem
-        .window("pushrod-render timer demo", 400, 180)
+        .window("pushrod-render timer demo", 400, 240)
         .position_centered()
         .opengl()
         .build()
@@ -42,19 +43,22 @@ pub fn main() {
         let timer1_pos = (_widgets[widget1_id]
             .widget
             .borrow_mut()
-            .get_numeric(CONFIG_PROGRESS)
+            .try_get(KEY_PROGRESS)
+            .unwrap_or_default()
             + 1)
             % 100;
         let timer2_pos = (_widgets[widget2_id]
             .widget
             .borrow_mut()
-            .get_numeric(CONFIG_PROGRESS)
+            .try_get(KEY_PROGRESS)
+            .unwrap_or_default()
             + 1)
             % 100;
         let timer3_pos = (_widgets[widget3_id]
             .widget
             .borrow_mut()
-            .get_numeric(CONFIG_PROGRESS)
+            .try_get(KEY_PROGRESS)
+            .unwrap_or_default()
             + 1)
             % 100;
 
@@ -72,12 +76,32 @@ pub fn main() {
             .set_numeric(CONFIG_PROGRESS, timer3_pos);
     });
 
-    engine.setup(500, 180);
+    let mut pause_button =
+        PushButtonWidget::new(20, 170, 360, 50, String::from("Pause/Resume"), 32);
+
+    pause_button.on_click(|_x, _widgets| {
+        let enabled = with_widget(_widgets, String::from("timer1"), |t: &mut TimerWidget| {
+            if t.is_enabled() {
+                t.disable();
+            } else {
+                t.enable();
+            }
+
+            t.is_enabled()
+        });
+
+        if let Some(enabled) = enabled {
+            eprintln!("Timer enabled: {}", enabled);
+        }
+    });
+
+    engine.setup(500, 240);
 
     engine.add_widget(Box::new(widget1), String::from("widget1"));
     engine.add_widget(Box::new(widget2), String::from("widget2"));
     engine.add_widget(Box::new(widget3), String::from("widget3"));
     engine.add_widget(Box::new(timer), String::from("timer1"));
+    engine.add_widget(Box::new(pause_button), String::from("pause_button"));
 
     engine.run(sdl_context, window);
 }
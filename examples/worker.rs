@@ -0,0 +1,57 @@
+extern crate pushrod;
+extern crate sdl2;
+
+use pushrod::render::engine::Engine;
+use pushrod::render::messages::WidgetTarget;
+use pushrod::render::widget::Widget;
+use pushrod::render::widget_config::{Config, CONFIG_COLOR_SECONDARY, CONFIG_PROGRESS};
+use pushrod::widgets::progress_widget::*;
+use sdl2::pixels::Color;
+use std::thread;
+use std::time::Duration;
+
+/*
+ * This demo updates a `ProgressWidget` from a background thread, using a `WidgetSender` to post
+ * the progress to the UI thread, instead of polling from a `TimerWidget`.
+ */
+
+pub fn main() {
+    let sdl_context = sdl2::init().unwrap();
+    let video_subsystem = sdl_context.video().unwrap();
+    let window = video_subsystem
+        .window("pushrod-render worker thread demo", 400, 80)
+        .position_centered()
+        .opengl()
+        .build()
+        .unwrap();
+    let mut engine = Engine::new();
+    let mut widget1 = ProgressWidget::new(20, 20, 360, 40);
+
+    widget1.set_color(CONFIG_COLOR_SECONDARY, Color::RGB(255, 0, 0));
+    widget1.set_numeric(CONFIG_PROGRESS, 0);
+
+    engine.setup(400, 80);
+
+    engine.add_widget(Box::new(widget1), String::from("widget1"));
+
+    let sender = engine.get_sender();
+
+    thread::spawn(move || {
+        for progress in 0..=100 {
+            if sender
+                .set_config(
+                    WidgetTarget::Name(String::from("widget1")),
+                    CONFIG_PROGRESS,
+                    Config::Numeric(progress),
+                )
+                .is_err()
+            {
+                break;
+            }
+
+            thread::sleep(Duration::from_millis(50));
+        }
+    });
+
+    engine.run(sdl_context, window);
+}
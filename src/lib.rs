@@ -15,8 +15,11 @@
 //! `pushrod` is a GUI library for Rust.
 //!
 //! # Dependencies
-//! Pushrod uses the following dependency:
+//! Pushrod uses the following dependencies:
 //! ```ignore
+//! [dependencies]
+//! log = "^0.4"
+//!
 //! [dependencies.sdl2]
 //! version = "^0.32"
 //! features = ["ttf", "image"]
@@ -33,6 +36,22 @@
 //! `pushrod::render` is the _core_ rendering components, containing the `Widget` base class, and
 //! drawing loop logic.
 //! `pushrod::widgets` is the extended `Widget` component library.
+//!
+//! # Features
+//! - `embedded-assets` (enabled by default): compiles the default font and the checkbox images
+//!   into the library, so applications run without an `assets` directory next to them.  Disable
+//!   it with `default-features = false` to load these assets from disk instead.
+//!
+//! # Logging
+//! Pushrod reports its diagnostics through the `log` facade, so nothing is printed unless your
+//! application installs a logger (ie. `env_logger`).  Messages are sent to the following targets:
+//! - `pushrod::events`: events received by the `Engine`, and how they are dispatched.
+//! - `pushrod::draw`: `Widget` redraws, and canvas presentation.
+//! - `pushrod::cache`: changes to the `WidgetCache`, such as added `Widget`s and focus changes.
+//! - `pushrod::resources`: fonts and images loaded into the `ResourceCache`.
+//! - `pushrod::config`: configuration values that are read as another type than they were set.
+//!
+//! With `env_logger`, for example, `RUST_LOG=pushrod::draw=trace` shows every `Widget` redraw.
 
 #[macro_use]
 
@@ -65,6 +84,13 @@ mod macros {
             fn get_callbacks(&mut self) -> &mut CallbackRegistry {
                 &mut self.callback_registry
             }
+
+            /// This function is a macro-created getter function that returns the `Widget` as an
+            /// `Any` reference, so that it can be downcast to its concrete type.  This code is
+            /// auto-generated using the `default_widget_properties!()` macro.
+            fn as_any(&mut self) -> &mut dyn ::std::any::Any {
+                self
+            }
         }
     }
 
@@ -138,6 +164,121 @@ mod macros {
                     }
                 }
             }
+
+            /// This function is a macro-created key pressed callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn key_pressed_callback(&mut self, _widgets: &[WidgetContainer], _keycode: sdl2::keyboard::Keycode, _keymod: sdl2::keyboard::Mod) {
+                if self.get_callbacks().has_on_key_pressed() {
+                    if let Some(mut cb) = self.get_callbacks().on_key_pressed.take() {
+                        cb(self, _widgets, _keycode, _keymod);
+                        self.get_callbacks().on_key_pressed = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created key released callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn key_released_callback(&mut self, _widgets: &[WidgetContainer], _keycode: sdl2::keyboard::Keycode, _keymod: sdl2::keyboard::Mod) {
+                if self.get_callbacks().has_on_key_released() {
+                    if let Some(mut cb) = self.get_callbacks().on_key_released.take() {
+                        cb(self, _widgets, _keycode, _keymod);
+                        self.get_callbacks().on_key_released = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created focus gained callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn focus_gained_callback(&mut self, _widgets: &[WidgetContainer]) {
+                if self.get_callbacks().has_on_focus_gained() {
+                    if let Some(mut cb) = self.get_callbacks().on_focus_gained.take() {
+                        cb(self, _widgets);
+                        self.get_callbacks().on_focus_gained = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created focus lost callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn focus_lost_callback(&mut self, _widgets: &[WidgetContainer]) {
+                if self.get_callbacks().has_on_focus_lost() {
+                    if let Some(mut cb) = self.get_callbacks().on_focus_lost.take() {
+                        cb(self, _widgets);
+                        self.get_callbacks().on_focus_lost = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created drag start callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn drag_start_callback(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+                if self.get_callbacks().has_on_drag_start() {
+                    if let Some(mut cb) = self.get_callbacks().on_drag_start.take() {
+                        cb(self, _widgets, _points);
+                        self.get_callbacks().on_drag_start = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created drag callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn drag_callback(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+                if self.get_callbacks().has_on_drag() {
+                    if let Some(mut cb) = self.get_callbacks().on_drag.take() {
+                        cb(self, _widgets, _points);
+                        self.get_callbacks().on_drag = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created drag end callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn drag_end_callback(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+                if self.get_callbacks().has_on_drag_end() {
+                    if let Some(mut cb) = self.get_callbacks().on_drag_end.take() {
+                        cb(self, _widgets, _points);
+                        self.get_callbacks().on_drag_end = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created drop entered callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn drop_entered_callback(&mut self, _widgets: &[WidgetContainer]) {
+                if self.get_callbacks().has_on_drop_entered() {
+                    if let Some(mut cb) = self.get_callbacks().on_drop_entered.take() {
+                        cb(self, _widgets);
+                        self.get_callbacks().on_drop_entered = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created drop exited callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn drop_exited_callback(&mut self, _widgets: &[WidgetContainer]) {
+                if self.get_callbacks().has_on_drop_exited() {
+                    if let Some(mut cb) = self.get_callbacks().on_drop_exited.take() {
+                        cb(self, _widgets);
+                        self.get_callbacks().on_drop_exited = Some(cb);
+                    }
+                }
+            }
+
+            /// This function is a macro-created drop callback override, created by the
+            /// `default_widget_callbacks!()` macro.
+            fn dropped_callback(
+                &mut self,
+                _widgets: &[WidgetContainer],
+                _payload: $crate::render::drag_drop::DragPayload,
+                _points: Points,
+            ) {
+                if self.get_callbacks().has_on_drop() {
+                    if let Some(mut cb) = self.get_callbacks().on_drop.take() {
+                        cb(self, _widgets, _payload, _points);
+                        self.get_callbacks().on_drop = Some(cb);
+                    }
+                }
+            }
         }
     }
 }
@@ -13,8 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::render::drag_drop::DragPayload;
 use crate::render::widget::Widget;
-use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_cache::{WidgetContainer, WidgetId};
+use sdl2::keyboard::{Keycode, Mod};
 
 /// This is an `FnMut` type that takes no additional parameters, returning a mutable reference
 /// to the current `Widget`, and borrowing the `WidgetContainer` list.
@@ -31,6 +33,18 @@ pub type FunctionPointParametersType =
 pub type FunctionClickParametersType =
     Option<Box<dyn FnMut(&mut dyn Widget, &[WidgetContainer], u8, u8, bool)>>;
 
+/// This is an `FnMut` that takes the `Keycode` of the key that was pressed or released, and the
+/// `Mod` modifier keys that were held at the time, returning a mutable reference to the current
+/// `Widget`, and borrowing the `WidgetContainer` list.
+pub type FunctionKeyParametersType =
+    Option<Box<dyn FnMut(&mut dyn Widget, &[WidgetContainer], Keycode, Mod)>>;
+
+/// This is an `FnMut` that takes the `DragPayload` that was dropped, and a `Point` as a `Vec<i32>`
+/// of the X and Y coordinates of the drop, returning a mutable reference to the current `Widget`,
+/// and borrowing the `WidgetContainer` list.
+pub type FunctionDropParametersType =
+    Option<Box<dyn FnMut(&mut dyn Widget, &[WidgetContainer], DragPayload, Vec<i32>)>>;
+
 /// This is a registry that contains a series of `FnMut` definitions for actions that can be applied
 /// to a `Widget`.  These can vary from a screen refresh (`tick`), to a mouse move event, etc.  Each
 /// callback gains access to the list of `WidgetContainer` objects stored by the cache.  This is
@@ -70,12 +84,62 @@ pub struct CallbackRegistry {
     /// the mouse button was pressed (`true`) or released (`false`).
     pub on_mouse_clicked: FunctionClickParametersType,
 
+    /// This function is called when a key is pressed while the `Widget` has focus.  It contains
+    /// the `Keycode` of the key, and the `Mod` modifier keys that were held down.
+    pub on_key_pressed: FunctionKeyParametersType,
+
+    /// This function is called when a key is released while the `Widget` has focus.  It contains
+    /// the `Keycode` of the key, and the `Mod` modifier keys that were held down.
+    pub on_key_released: FunctionKeyParametersType,
+
+    /// This function is called when a `Widget` receives keyboard focus.
+    pub on_focus_gained: FunctionNoParametersType,
+
+    /// This function is called when a `Widget` loses keyboard focus.
+    pub on_focus_lost: FunctionNoParametersType,
+
+    /// This function is called when the mouse first moves while a button is held down over the
+    /// `Widget`.  It contains the X and Y coordinates of where the button was pressed.
+    pub on_drag_start: FunctionPointParametersType,
+
+    /// This function is called each time the mouse moves during a drag that started on the
+    /// `Widget`, even if the mouse is outside of its bounds.  It contains the X and Y coordinates
+    /// of the mouse.
+    pub on_drag: FunctionPointParametersType,
+
+    /// This function is called when the mouse button that started a drag is released.  It
+    /// contains the X and Y coordinates of where the button was released.
+    pub on_drag_end: FunctionPointParametersType,
+
+    /// This function is called when a drag and drop operation that this `Widget` accepts moves
+    /// over it, so that it can show that it is a drop target.
+    pub on_drop_entered: FunctionNoParametersType,
+
+    /// This function is called when a drag and drop operation moves away from this `Widget`, is
+    /// dropped on it, or is cancelled, so that it can remove its drop target feedback.
+    pub on_drop_exited: FunctionNoParametersType,
+
+    /// This function is called when a `DragPayload` is dropped on this `Widget`.  It contains the
+    /// payload, and the X and Y coordinates of the drop.  Setting this callback makes the `Widget`
+    /// accept drops.
+    pub on_drop: FunctionDropParametersType,
+
     has_on_tick: bool,
     has_on_mouse_entered: bool,
     has_on_mouse_exited: bool,
     has_on_mouse_moved: bool,
     has_on_mouse_scrolled: bool,
     has_on_mouse_clicked: bool,
+    has_on_key_pressed: bool,
+    has_on_key_released: bool,
+    has_on_focus_gained: bool,
+    has_on_focus_lost: bool,
+    has_on_drag_start: bool,
+    has_on_drag: bool,
+    has_on_drag_end: bool,
+    has_on_drop_entered: bool,
+    has_on_drop_exited: bool,
+    has_on_drop: bool,
 }
 
 /// Implementation of the `CallbackRegistry`.
@@ -89,12 +153,32 @@ impl CallbackRegistry {
             on_mouse_moved: None,
             on_mouse_scrolled: None,
             on_mouse_clicked: None,
+            on_key_pressed: None,
+            on_key_released: None,
+            on_focus_gained: None,
+            on_focus_lost: None,
+            on_drag_start: None,
+            on_drag: None,
+            on_drag_end: None,
+            on_drop_entered: None,
+            on_drop_exited: None,
+            on_drop: None,
             has_on_tick: false,
             has_on_mouse_entered: false,
             has_on_mouse_exited: false,
             has_on_mouse_moved: false,
             has_on_mouse_scrolled: false,
             has_on_mouse_clicked: false,
+            has_on_key_pressed: false,
+            has_on_key_released: false,
+            has_on_focus_gained: false,
+            has_on_focus_lost: false,
+            has_on_drag_start: false,
+            has_on_drag: false,
+            has_on_drag_end: false,
+            has_on_drop_entered: false,
+            has_on_drop_exited: false,
+            has_on_drop: false,
         }
     }
 
@@ -158,6 +242,106 @@ impl CallbackRegistry {
         self.has_on_mouse_clicked = true;
     }
 
+    /// Assigns an `FnMut` that will be called when a key is pressed while the `Widget` has focus.
+    /// If this is not set, this function will be bypassed.
+    pub fn on_key_pressed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], Keycode, Mod) + 'static,
+    {
+        self.on_key_pressed = Some(Box::new(callback));
+        self.has_on_key_pressed = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a key is released while the `Widget` has focus.
+    /// If this is not set, this function will be bypassed.
+    pub fn on_key_released<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], Keycode, Mod) + 'static,
+    {
+        self.on_key_released = Some(Box::new(callback));
+        self.has_on_key_released = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when the `Widget` receives keyboard focus.  If this
+    /// is not set, this function will be bypassed.
+    pub fn on_focus_gained<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer]) + 'static,
+    {
+        self.on_focus_gained = Some(Box::new(callback));
+        self.has_on_focus_gained = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when the `Widget` loses keyboard focus.  If this
+    /// is not set, this function will be bypassed.
+    pub fn on_focus_lost<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer]) + 'static,
+    {
+        self.on_focus_lost = Some(Box::new(callback));
+        self.has_on_focus_lost = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a drag starts on the `Widget`.  If this is not
+    /// set, this function will be bypassed.
+    pub fn on_drag_start<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], Vec<i32>) + 'static,
+    {
+        self.on_drag_start = Some(Box::new(callback));
+        self.has_on_drag_start = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when the mouse moves during a drag that started on
+    /// the `Widget`.  If this is not set, this function will be bypassed.
+    pub fn on_drag<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], Vec<i32>) + 'static,
+    {
+        self.on_drag = Some(Box::new(callback));
+        self.has_on_drag = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a drag that started on the `Widget` ends.  If
+    /// this is not set, this function will be bypassed.
+    pub fn on_drag_end<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], Vec<i32>) + 'static,
+    {
+        self.on_drag_end = Some(Box::new(callback));
+        self.has_on_drag_end = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when an accepted drag and drop operation moves over
+    /// the `Widget`.  If this is not set, this function will be bypassed.
+    pub fn on_drop_entered<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer]) + 'static,
+    {
+        self.on_drop_entered = Some(Box::new(callback));
+        self.has_on_drop_entered = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when an accepted drag and drop operation leaves the
+    /// `Widget`.  If this is not set, this function will be bypassed.
+    pub fn on_drop_exited<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer]) + 'static,
+    {
+        self.on_drop_exited = Some(Box::new(callback));
+        self.has_on_drop_exited = true;
+    }
+
+    /// Assigns an `FnMut` that will be called when a `DragPayload` is dropped on the `Widget`.  If
+    /// this is not set, this function will be bypassed.
+    pub fn on_drop<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut dyn Widget, &[WidgetContainer], DragPayload, Vec<i32>) + 'static,
+    {
+        self.on_drop = Some(Box::new(callback));
+        self.has_on_drop = true;
+    }
+
     /// Tells the `Widget` whether or not an `on_tick` callback has been set.
     pub fn has_on_tick(&mut self) -> bool {
         self.has_on_tick
@@ -187,6 +371,56 @@ impl CallbackRegistry {
     pub fn has_on_mouse_clicked(&mut self) -> bool {
         self.has_on_mouse_clicked
     }
+
+    /// Tells the `Widget` whether or not an `on_key_pressed` callback has been set.
+    pub fn has_on_key_pressed(&mut self) -> bool {
+        self.has_on_key_pressed
+    }
+
+    /// Tells the `Widget` whether or not an `on_key_released` callback has been set.
+    pub fn has_on_key_released(&mut self) -> bool {
+        self.has_on_key_released
+    }
+
+    /// Tells the `Widget` whether or not an `on_focus_gained` callback has been set.
+    pub fn has_on_focus_gained(&mut self) -> bool {
+        self.has_on_focus_gained
+    }
+
+    /// Tells the `Widget` whether or not an `on_focus_lost` callback has been set.
+    pub fn has_on_focus_lost(&mut self) -> bool {
+        self.has_on_focus_lost
+    }
+
+    /// Tells the `Widget` whether or not an `on_drag_start` callback has been set.
+    pub fn has_on_drag_start(&mut self) -> bool {
+        self.has_on_drag_start
+    }
+
+    /// Tells the `Widget` whether or not an `on_drag` callback has been set.
+    pub fn has_on_drag(&mut self) -> bool {
+        self.has_on_drag
+    }
+
+    /// Tells the `Widget` whether or not an `on_drag_end` callback has been set.
+    pub fn has_on_drag_end(&mut self) -> bool {
+        self.has_on_drag_end
+    }
+
+    /// Tells the `Widget` whether or not an `on_drop_entered` callback has been set.
+    pub fn has_on_drop_entered(&mut self) -> bool {
+        self.has_on_drop_entered
+    }
+
+    /// Tells the `Widget` whether or not an `on_drop_exited` callback has been set.
+    pub fn has_on_drop_exited(&mut self) -> bool {
+        self.has_on_drop_exited
+    }
+
+    /// Tells the `Widget` whether or not an `on_drop` callback has been set.
+    pub fn has_on_drop(&mut self) -> bool {
+        self.has_on_drop
+    }
 }
 
 pub fn widget_id_for_name(widgets: &[WidgetContainer], name: String) -> usize {
@@ -195,3 +429,34 @@ pub fn widget_id_for_name(widgets: &[WidgetContainer], name: String) -> usize {
         None => 0 as usize,
     }
 }
+
+/// Finds the `Widget` named `name` in the `WidgetContainer` list, and calls `f` with it, downcast to
+/// its concrete type `T`, returning the result.  Use this in callbacks to call methods that are
+/// specific to a `Widget` type on another `Widget`, ie.
+/// `with_widget(_widgets, String::from("timer1"), |t: &mut TimerWidget| t.disable())`.  Returns
+/// `None` if no `Widget` has that name, if it is not of type `T`, or if it is the `Widget` whose
+/// callback is running.  Use the `Widget` passed to the callback to access that `Widget` instead.
+pub fn with_widget<T, R, F>(widgets: &[WidgetContainer], name: String, f: F) -> Option<R>
+where
+    T: Widget + 'static,
+    F: FnOnce(&mut T) -> R,
+{
+    widgets
+        .iter()
+        .find(|x| x.get_widget_name() == name)
+        .and_then(|x| x.with_widget(f))
+}
+
+/// Finds the `Widget` with the `WidgetId` `widget_id` in the `WidgetContainer` list, and calls `f`
+/// with it, downcast to its concrete type `T`, returning the result.  Returns `None` under the same
+/// conditions as `with_widget`, or if the `WidgetId` is stale.
+pub fn with_widget_id<T, R, F>(widgets: &[WidgetContainer], widget_id: WidgetId, f: F) -> Option<R>
+where
+    T: Widget + 'static,
+    F: FnOnce(&mut T) -> R,
+{
+    widgets
+        .iter()
+        .find(|x| x.get_id() == widget_id)
+        .and_then(|x| x.with_widget(f))
+}
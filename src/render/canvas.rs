@@ -0,0 +1,499 @@
+// Pushrod Rendering Library
+// Render Target Abstraction
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::resources::{CachedImage, ResourceCache};
+use crate::render::widget_cache::WidgetId;
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::render::{Canvas, RenderTarget, Texture, TextureCreator};
+use sdl2::surface::{Surface, SurfaceRef};
+use sdl2::video::Window;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// This trait is the drawing surface that is handed to `Widget`s during the draw loop.  It exposes
+/// the drawing primitives that `Widget`s use, independent of where the drawing ends up.  It is
+/// implemented for SDL2's `Canvas<Window>`, which draws to the screen, and `Canvas<Surface>`,
+/// which draws to an in-memory `Surface` without the need for a display.
+///
+/// Textures are bound to the `Canvas` that created them, so `Widget`s that need to draw images or
+/// rendered text should hand their `Surface` to `copy_surface`, which converts it to a texture for
+/// the target that is being drawn to.  Images from the `ResourceCache` should be handed to
+/// `copy_image` instead, which allows canvases that keep a `TextureCache` to convert each image
+/// only once.
+pub trait RenderCanvas {
+    /// Sets the color used for drawing operations.
+    fn set_draw_color(&mut self, color: Color);
+
+    /// Returns the color currently used for drawing operations.
+    fn draw_color(&self) -> Color;
+
+    /// Fills the entire target with the current draw color.
+    fn clear(&mut self);
+
+    /// Presents the drawing performed since the last `present` call.
+    fn present(&mut self);
+
+    /// Draws the outline of a rectangle using the current draw color.
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String>;
+
+    /// Fills a rectangle using the current draw color.
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String>;
+
+    /// Draws a line between two points using the current draw color.
+    fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String>;
+
+    /// Sets the clipping rectangle.  `None` disables clipping.
+    fn set_clip_rect(&mut self, rect: Option<Rect>);
+
+    /// Returns the current clipping rectangle, or `None` if clipping is disabled.
+    fn clip_rect(&self) -> Option<Rect>;
+
+    /// Copies the `src` area of a `Surface` to the `dst` area of the target, scaling it if the
+    /// sizes differ.  A `src` of `None` copies the entire `Surface`.
+    fn copy_surface(
+        &mut self,
+        surface: &SurfaceRef,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String>;
+
+    /// Copies the `src` area of an image that was loaded from `path` to the `dst` area of the
+    /// target, in the same way as `copy_surface`.  Canvases that keep a `TextureCache` reuse the
+    /// texture that the image was converted to the last time it was drawn.  By default, this calls
+    /// `copy_surface`.
+    fn copy_image(
+        &mut self,
+        _path: &str,
+        image: &CachedImage,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        self.copy_surface(image, src, dst)
+    }
+
+    /// Copies the `src` area of the render cache of the `Widget` specified by `id` to the `dst`
+    /// area of the target, in the same way as `copy_surface`.  Canvases that keep a
+    /// `TextureCache` reuse the texture that the render cache was converted to, until the
+    /// `Widget` is drawn into a new render cache.  By default, this calls `copy_surface`.
+    fn copy_render_cache(
+        &mut self,
+        _id: WidgetId,
+        surface: &CachedImage,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        self.copy_surface(surface, src, dst)
+    }
+
+    /// Returns the `ResourceCache` that fonts and images should be loaded from while drawing to
+    /// this canvas, or `None` if there is none, in which case they cannot be loaded.  The
+    /// `WidgetCache` sets its own `ResourceCache` on the canvases that it draws `Widget`s to.
+    fn resources(&mut self) -> Option<&mut ResourceCache> {
+        None
+    }
+}
+
+/// Implements `RenderCanvas` for a `Canvas` of the given `RenderTarget` type.  The implementations
+/// are identical, but `texture_creator` is not available generically over all `RenderTarget`s.
+macro_rules! impl_render_canvas {
+    ($target:ty) => {
+        fn set_draw_color(&mut self, color: Color) {
+            Canvas::<$target>::set_draw_color(self, color);
+        }
+
+        fn draw_color(&self) -> Color {
+            Canvas::<$target>::draw_color(self)
+        }
+
+        fn clear(&mut self) {
+            Canvas::<$target>::clear(self);
+        }
+
+        fn present(&mut self) {
+            Canvas::<$target>::present(self);
+        }
+
+        fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+            Canvas::<$target>::draw_rect(self, rect)
+        }
+
+        fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+            Canvas::<$target>::fill_rect(self, rect)
+        }
+
+        fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String> {
+            Canvas::<$target>::draw_line(self, start, end)
+        }
+
+        fn set_clip_rect(&mut self, rect: Option<Rect>) {
+            Canvas::<$target>::set_clip_rect(self, rect);
+        }
+
+        fn clip_rect(&self) -> Option<Rect> {
+            Canvas::<$target>::clip_rect(self)
+        }
+
+        fn copy_surface(
+            &mut self,
+            surface: &SurfaceRef,
+            src: Option<Rect>,
+            dst: Rect,
+        ) -> Result<(), String> {
+            let texture_creator = self.texture_creator();
+            let texture = texture_creator
+                .create_texture_from_surface(surface)
+                .map_err(|e| e.to_string())?;
+
+            self.copy(&texture, src, dst)
+        }
+    };
+}
+
+/// `RenderCanvas` implementation that draws to a `Window`.
+impl RenderCanvas for Canvas<Window> {
+    impl_render_canvas!(Window);
+}
+
+/// `RenderCanvas` implementation that draws to an in-memory `Surface`.
+impl<'s> RenderCanvas for Canvas<Surface<'s>> {
+    impl_render_canvas!(Surface<'s>);
+}
+
+/// This is a `RenderCanvas` that draws to another `RenderCanvas`, translating all coordinates by
+/// an offset.  It is used to draw child `Widget`s, whose coordinates are relative to their parent.
+/// Clipping rectangles set through an `OffsetCanvas` are limited to its `bounds`, if set, so that
+/// a `Widget` cannot draw outside of the area of its parent.  An `OffsetCanvas` can also provide a
+/// `ResourceCache`, otherwise, the `ResourceCache` of the canvas it draws to is used.
+pub struct OffsetCanvas<'a> {
+    canvas: &'a mut dyn RenderCanvas,
+    x: i32,
+    y: i32,
+    bounds: Option<Rect>,
+    resources: Option<&'a mut ResourceCache>,
+}
+
+/// This is the implementation of the `OffsetCanvas`.
+impl<'a> OffsetCanvas<'a> {
+    /// Creates a new `OffsetCanvas` that draws to `canvas`, adding `x` and `y` to all coordinates.
+    /// `bounds` is the area of `canvas`, in its own coordinates, that drawing is limited to.
+    pub fn new(canvas: &'a mut dyn RenderCanvas, x: i32, y: i32, bounds: Option<Rect>) -> Self {
+        Self {
+            canvas,
+            x,
+            y,
+            bounds,
+            resources: None,
+        }
+    }
+
+    /// Sets the `ResourceCache` that is returned by `resources`.
+    pub fn set_resources(&mut self, resources: &'a mut ResourceCache) {
+        self.resources = Some(resources);
+    }
+
+    fn translate(&self, rect: Rect) -> Rect {
+        Rect::new(
+            rect.x() + self.x,
+            rect.y() + self.y,
+            rect.width(),
+            rect.height(),
+        )
+    }
+}
+
+/// `RenderCanvas` implementation that translates coordinates before drawing.
+impl<'a> RenderCanvas for OffsetCanvas<'a> {
+    fn set_draw_color(&mut self, color: Color) {
+        self.canvas.set_draw_color(color);
+    }
+
+    fn draw_color(&self) -> Color {
+        self.canvas.draw_color()
+    }
+
+    fn clear(&mut self) {
+        self.canvas.clear();
+    }
+
+    fn present(&mut self) {
+        self.canvas.present();
+    }
+
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+        let rect = self.translate(rect);
+
+        self.canvas.draw_rect(rect)
+    }
+
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+        let rect = self.translate(rect);
+
+        self.canvas.fill_rect(rect)
+    }
+
+    fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String> {
+        let offset = Point::new(self.x, self.y);
+
+        self.canvas.draw_line(start + offset, end + offset)
+    }
+
+    fn set_clip_rect(&mut self, rect: Option<Rect>) {
+        // SDL2 cannot represent an empty clipping rectangle, so a clip that lies entirely outside
+        // of the bounds is moved off the top-left corner of the target instead.
+        let clip = match (rect.map(|r| self.translate(r)), self.bounds) {
+            (Some(rect), Some(bounds)) => rect
+                .intersection(bounds)
+                .or_else(|| Some(Rect::new(-1, -1, 1, 1))),
+            (Some(rect), None) => Some(rect),
+            (None, bounds) => bounds,
+        };
+
+        self.canvas.set_clip_rect(clip);
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        self.canvas
+            .clip_rect()
+            .map(|r| Rect::new(r.x() - self.x, r.y() - self.y, r.width(), r.height()))
+    }
+
+    fn copy_surface(
+        &mut self,
+        surface: &SurfaceRef,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        let dst = self.translate(dst);
+
+        self.canvas.copy_surface(surface, src, dst)
+    }
+
+    fn copy_image(
+        &mut self,
+        path: &str,
+        image: &CachedImage,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        let dst = self.translate(dst);
+
+        self.canvas.copy_image(path, image, src, dst)
+    }
+
+    fn copy_render_cache(
+        &mut self,
+        id: WidgetId,
+        surface: &CachedImage,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        let dst = self.translate(dst);
+
+        self.canvas.copy_render_cache(id, surface, src, dst)
+    }
+
+    fn resources(&mut self) -> Option<&mut ResourceCache> {
+        match self.resources {
+            Some(ref mut resources) => Some(resources),
+            None => self.canvas.resources(),
+        }
+    }
+}
+
+/// This is a cache of the textures that images and render caches are converted to when they are
+/// drawn with `copy_image` and `copy_render_cache`, so that each is only converted once.  Textures
+/// can only be drawn to the `Canvas` whose `TextureCreator` created them, so a `TextureCache`
+/// borrows the `TextureCreator` of a single `Canvas`, and is used with that `Canvas` through a
+/// `TextureCanvas`.  Textures of images are keyed by the path of their image, and those of render
+/// caches by the `WidgetId` of their `Widget`.  A texture is converted again when the `Surface` it
+/// was converted from is replaced, ie. when the `ResourceCache` loads the image again, or the
+/// `Widget` is drawn into a new render cache after it was invalidated or resized.  Textures whose
+/// `Surface` is no longer used elsewhere are released.
+pub struct TextureCache<'r, T> {
+    texture_creator: &'r TextureCreator<T>,
+    images: HashMap<String, (CachedImage, Texture<'r>)>,
+    render_caches: HashMap<WidgetId, (CachedImage, Texture<'r>)>,
+}
+
+/// This is the implementation of the `TextureCache`.
+impl<'r, T> TextureCache<'r, T> {
+    /// Creates a new, empty `TextureCache` for the `Canvas` that `texture_creator` belongs to.
+    pub fn new(texture_creator: &'r TextureCreator<T>) -> Self {
+        Self {
+            texture_creator,
+            images: HashMap::new(),
+            render_caches: HashMap::new(),
+        }
+    }
+
+    /// Removes all textures from the cache, so that they are converted again the next time they
+    /// are drawn.
+    pub fn clear(&mut self) {
+        self.images.clear();
+        self.render_caches.clear();
+    }
+
+    /// Returns the texture for the image loaded from `path`.
+    fn get_image(&mut self, path: &str, image: &CachedImage) -> Result<&Texture<'r>, String> {
+        get_texture(
+            self.texture_creator,
+            &mut self.images,
+            String::from(path),
+            image,
+        )
+    }
+
+    /// Returns the texture for the render cache of the `Widget` specified by `id`.
+    fn get_render_cache(
+        &mut self,
+        id: WidgetId,
+        surface: &CachedImage,
+    ) -> Result<&Texture<'r>, String> {
+        get_texture(self.texture_creator, &mut self.render_caches, id, surface)
+    }
+}
+
+/// Returns the texture for `key` from `textures`, converting `surface` if there is no texture for
+/// `key`, or if it was converted from another `Surface`.
+fn get_texture<'t, 'r, T, K: Clone + Eq + Hash>(
+    texture_creator: &'r TextureCreator<T>,
+    textures: &'t mut HashMap<K, (CachedImage, Texture<'r>)>,
+    key: K,
+    surface: &CachedImage,
+) -> Result<&'t Texture<'r>, String> {
+    let current = match textures.get(&key) {
+        Some((cached_surface, _)) => Rc::ptr_eq(cached_surface, surface),
+        None => false,
+    };
+
+    if !current {
+        let texture = texture_creator
+            .create_texture_from_surface(&**surface)
+            .map_err(|e| e.to_string())?;
+
+        textures.retain(|_, (cached_surface, _)| Rc::strong_count(cached_surface) > 1);
+        textures.insert(key.clone(), (surface.clone(), texture));
+    }
+
+    Ok(&textures[&key].1)
+}
+
+/// This is a `RenderCanvas` that draws to a `Canvas`, and draws images and render caches through a
+/// `TextureCache` that belongs to that `Canvas`.  The `Engine` draws to its `Window` through a `TextureCanvas`.
+/// Applications that call `Engine::draw` from their own loop can do the same, by keeping a
+/// `TextureCache` for their `Canvas`:
+///
+/// ```ignore
+/// let texture_creator = canvas.texture_creator();
+/// let mut textures = TextureCache::new(&texture_creator);
+///
+/// // Once per frame:
+/// engine.draw(&mut TextureCanvas::new(&mut canvas, &mut textures));
+/// canvas.present();
+/// ```
+pub struct TextureCanvas<'a, 'r, T: RenderTarget> {
+    canvas: &'a mut Canvas<T>,
+    textures: &'a mut TextureCache<'r, T::Context>,
+}
+
+/// This is the implementation of the `TextureCanvas`.
+impl<'a, 'r, T: RenderTarget> TextureCanvas<'a, 'r, T> {
+    /// Creates a new `TextureCanvas` that draws to `canvas`, keeping the textures of the images
+    /// that it draws in `textures`, which must have been created for `canvas`.
+    pub fn new(canvas: &'a mut Canvas<T>, textures: &'a mut TextureCache<'r, T::Context>) -> Self {
+        Self { canvas, textures }
+    }
+}
+
+/// `RenderCanvas` implementation that draws images through the `TextureCache`.
+impl<'a, 'r, T: RenderTarget> RenderCanvas for TextureCanvas<'a, 'r, T>
+where
+    Canvas<T>: RenderCanvas,
+{
+    fn set_draw_color(&mut self, color: Color) {
+        RenderCanvas::set_draw_color(self.canvas, color);
+    }
+
+    fn draw_color(&self) -> Color {
+        RenderCanvas::draw_color(self.canvas)
+    }
+
+    fn clear(&mut self) {
+        RenderCanvas::clear(self.canvas);
+    }
+
+    fn present(&mut self) {
+        RenderCanvas::present(self.canvas);
+    }
+
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+        RenderCanvas::draw_rect(self.canvas, rect)
+    }
+
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+        RenderCanvas::fill_rect(self.canvas, rect)
+    }
+
+    fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String> {
+        RenderCanvas::draw_line(self.canvas, start, end)
+    }
+
+    fn set_clip_rect(&mut self, rect: Option<Rect>) {
+        RenderCanvas::set_clip_rect(self.canvas, rect);
+    }
+
+    fn clip_rect(&self) -> Option<Rect> {
+        RenderCanvas::clip_rect(self.canvas)
+    }
+
+    fn copy_surface(
+        &mut self,
+        surface: &SurfaceRef,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        RenderCanvas::copy_surface(self.canvas, surface, src, dst)
+    }
+
+    fn copy_image(
+        &mut self,
+        path: &str,
+        image: &CachedImage,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        let texture = self.textures.get_image(path, image)?;
+
+        self.canvas.copy(texture, src, dst)
+    }
+
+    fn copy_render_cache(
+        &mut self,
+        id: WidgetId,
+        surface: &CachedImage,
+        src: Option<Rect>,
+        dst: Rect,
+    ) -> Result<(), String> {
+        let texture = self.textures.get_render_cache(id, surface)?;
+
+        self.canvas.copy(texture, src, dst)
+    }
+
+    fn resources(&mut self) -> Option<&mut ResourceCache> {
+        RenderCanvas::resources(self.canvas)
+    }
+}
@@ -0,0 +1,61 @@
+// Pushrod Rendering Library
+// Drag and Drop Payloads
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::rc::Rc;
+
+/// This is the data that is carried by a drag and drop operation.  A `Widget` starts a drag and
+/// drop operation by returning a payload from `drag_payload`, and a drop target receives it in
+/// `dropped`.  Files dropped onto the window from the desktop are delivered as `File` payloads.
+/// Text dropped from other applications is not delivered, as SDL2 0.32 does not report it, so
+/// `Text` payloads only come from `Widget`s.
+#[derive(Clone)]
+pub enum DragPayload {
+    /// A `String` of text.
+    Text(String),
+
+    /// The path of a file.
+    File(String),
+
+    /// An application-defined value.  Use `downcast_ref` on the value to retrieve it.
+    Custom(Rc<dyn Any>),
+}
+
+/// This is the implementation of the `DragPayload`.
+impl DragPayload {
+    /// Returns the text of a `Text` payload, or `None` for any other payload type.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            DragPayload::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the path of a `File` payload, or `None` for any other payload type.
+    pub fn as_file(&self) -> Option<&str> {
+        match self {
+            DragPayload::File(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `Custom` payload if it is of type `T`, or `None` otherwise.
+    pub fn as_custom<T: 'static>(&self) -> Option<&T> {
+        match self {
+            DragPayload::Custom(value) => value.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+}
@@ -13,19 +13,149 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use log::{debug, error, trace, warn};
 use sdl2::event::Event;
-//use sdl2::messagebox::*;
-use sdl2::video::Window;
-use sdl2::Sdl;
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Rect;
+use sdl2::render::Canvas;
+use sdl2::surface::Surface;
+use sdl2::sys::SDL_EventType;
+use sdl2::video::{FullscreenType, Window};
+use sdl2::{EventPump, Sdl};
 
+use crate::render::canvas::{RenderCanvas, TextureCache, TextureCanvas};
+use crate::render::drag_drop::DragPayload;
+use crate::render::error::PushrodError;
+use crate::render::messages::{WidgetMessage, WidgetSender};
+use crate::render::observable::{BindingMode, Observable};
+use crate::render::resources::ResourceCache;
+use crate::render::signals::{PendingSignal, Signal, SignalSlots, SlotId};
 use crate::render::widget::{BaseWidget, Widget};
-use crate::render::widget_cache::WidgetCache;
-use std::time::Duration;
+use crate::render::widget_cache::{WidgetCache, WidgetContainer, WidgetId};
+use crate::render::widget_config::{ConfigKey, ConfigValue};
+use crate::render::Points;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant};
+
+/// This is the default number of frames per second that `run` targets.
+pub const DEFAULT_FRAME_RATE: u32 = 60;
+
+/// This is the maximum amount of time, in milliseconds, that `run` blocks waiting for an event
+/// while in idle mode.
+const IDLE_WAIT_TIMEOUT_MS: u32 = 250;
+
+/// This is the maximum number of times that signals are delivered in a row, when slots emit
+/// further signals.  Signals that remain are delivered the next time requests are processed, so
+/// that signals that trigger each other cannot stall the `Engine`.
+const MAX_SIGNAL_ROUNDS: u32 = 16;
+
+/// This is the callback type that is used when the `Engine` is asked to quit.  Returning `false`
+/// from the callback cancels the shutdown.
+pub type OnQuitRequestedCallbackType = Option<Box<dyn FnMut(&[WidgetContainer]) -> bool>>;
+
+/// This is the callback type that is used when the `Engine` encounters an error.  The `WidgetId`
+/// is that of the `Widget` that failed to draw, or `None` if the error did not come from a
+/// `Widget`.
+pub type OnErrorCallbackType = Option<Box<dyn FnMut(Option<WidgetId>, &PushrodError)>>;
+
+/// This is the storage for requests made through an `EngineHandle`, which are applied by the
+/// `Engine` once the current callback has finished.
+#[derive(Default)]
+struct EngineRequests {
+    quit: bool,
+    repaint: bool,
+    window_title: Option<String>,
+    fullscreen: Option<bool>,
+    removals: Vec<WidgetId>,
+    restacks: Vec<(WidgetId, bool)>,
+    signals: Vec<PendingSignal>,
+}
+
+/// This is a handle to a running `Engine`, which can be obtained by calling `get_handle`.  It can
+/// be cloned and moved into callbacks, so that a `Widget` can control the `Engine` - for example,
+/// a "Quit" button can stop the application.  Requests are queued, and applied by the `Engine`
+/// after the current callback returns.
+#[derive(Clone, Default)]
+pub struct EngineHandle {
+    requests: Rc<RefCell<EngineRequests>>,
+}
+
+/// This is the implementation of the `EngineHandle`.
+impl EngineHandle {
+    /// Requests that the `Engine` stops running.  The `on_quit_requested` callback, if set, is
+    /// consulted first, and may cancel the request.
+    pub fn quit(&self) {
+        self.requests.borrow_mut().quit = true;
+    }
+
+    /// Requests that every `Widget` is redrawn on the next draw loop.
+    pub fn request_repaint(&self) {
+        self.requests.borrow_mut().repaint = true;
+    }
+
+    /// Requests that the title of the `Window` is changed to `title`.
+    pub fn set_window_title(&self, title: String) {
+        self.requests.borrow_mut().window_title = Some(title);
+    }
+
+    /// Requests that the `Window` enters (`true`) or leaves (`false`) fullscreen mode.
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        self.requests.borrow_mut().fullscreen = Some(fullscreen);
+    }
+
+    /// Requests that the `Widget` specified by `widget_id` is removed from the display list, along
+    /// with all of its children.  Use this to remove `Widget`s from within callbacks, where the
+    /// `Engine` is not available.  Stale IDs are ignored.
+    pub fn remove_widget(&self, widget_id: WidgetId) {
+        self.requests.borrow_mut().removals.push(widget_id);
+    }
+
+    /// Requests that the `Widget` specified by `widget_id` is moved above all of its siblings in
+    /// the same layer, ie. when a popup is opened.  Stale IDs are ignored.
+    pub fn bring_to_front(&self, widget_id: WidgetId) {
+        self.requests.borrow_mut().restacks.push((widget_id, true));
+    }
+
+    /// Requests that the `Widget` specified by `widget_id` is moved below all of its siblings in
+    /// the same layer.  Stale IDs are ignored.
+    pub fn send_to_back(&self, widget_id: WidgetId) {
+        self.requests.borrow_mut().restacks.push((widget_id, false));
+    }
+
+    /// Emits `signal` with the `payload` given.  The signal is delivered to the slots that are
+    /// connected to it once the current callback returns, so slots are free to access any
+    /// `Widget`, including the one that emitted the signal.
+    pub fn emit<T: 'static>(&self, signal: Signal<T>, payload: T) {
+        self.requests
+            .borrow_mut()
+            .signals
+            .push(PendingSignal::new(signal, payload));
+    }
+}
 
 /// This is a storage container for the Pushrod event engine.
 pub struct Engine {
     cache: WidgetCache,
     current_widget_id: i32,
+    captured_widget_id: i32,
+    captured_button: u8,
+    captured_inside: bool,
+    drag_origin: Points,
+    dragging: bool,
+    drag_payload: Option<DragPayload>,
+    drop_target_id: i32,
+    mouse_point: Points,
+    running: bool,
+    frame_rate: u32,
+    idle_mode: bool,
+    handle: EngineHandle,
+    on_quit_requested: OnQuitRequestedCallbackType,
+    on_error: OnErrorCallbackType,
+    signal_slots: SignalSlots,
+    message_sender: Sender<WidgetMessage>,
+    message_receiver: Receiver<WidgetMessage>,
 }
 
 /// This is the heart of the Pushrod event engine, and is what is used to drive the interaction
@@ -44,18 +174,112 @@ pub struct Engine {
 /// Call `add_widget(Box::new(widget), "name".to_string())` to add your `Widget` to the managed
 /// display list.
 ///
+/// ## Stacking Order
+/// `Widget`s are drawn in the order in which they were added, and the top-most `Widget` under the
+/// mouse receives mouse events.  Use `raise`, `lower`, `bring_to_front` and `send_to_back` to change
+/// the order of a `Widget` among its siblings.  `Widget`s added with `add_overlay` are drawn above
+/// all other `Widget`s, which makes them suitable for popups, dropdown lists and tooltips.
+///
+/// ## Keyboard Focus
+/// Clicking on a `Widget` gives it keyboard focus, and all key presses and releases are then sent
+/// to that `Widget`.  Focus can also be assigned programmatically by calling `set_focus(id)`.
+///
+/// ## Rendering Without a Window
+/// Calling `render_to_surface(width, height)` draws every `Widget` in the display list to an
+/// in-memory `Surface` instead of a `Window`.  This requires no display, so it can be used to
+/// render `Widget`s in automated tests, or to create thumbnails of an application's screen.
+///
 /// ## Call Run()
 /// Calling `run(sdl, window)` will manage the application screen after all widgets have been added
 /// to the display list.
 ///
+/// ## Mouse Capture
+/// When a mouse button is pressed over a `Widget`, that `Widget` captures the mouse until the same
+/// button is released.  While captured, all mouse motion, button and scroll events are sent to the
+/// capturing `Widget`, even when the mouse leaves its bounds, and other `Widget`s receive no
+/// `mouse_entered` or `mouse_exited` calls.  The capturing `Widget` receives `mouse_exited` and
+/// `mouse_entered` as the mouse leaves and re-enters its bounds, and `drag_start`, `drag` and
+/// `drag_end` as the mouse is dragged.
+///
+/// ## Drag and Drop
+/// A `Widget` starts a drag and drop operation by returning a `DragPayload` from `drag_payload`
+/// when a drag starts on it.  As the payload is dragged, the `Widget` under the mouse receives
+/// `drop_entered` and `drop_exited` calls if its `accepts_drop` returns `true`, and `dropped` when
+/// the mouse button is released over it.  Setting an `on_drop` callback is enough to accept all
+/// payloads.  Files dropped onto the window from the desktop are delivered to the `Widget` under the
+/// mouse as `DragPayload::File`.  Text dropped from the desktop is not reported by SDL2 0.32, so it
+/// is not supported.
+///
+/// ## Frame Pacing
+/// `run` targets 60 frames per second by default, which can be changed with `set_frame_rate`.
+/// Applications that spend most of their time waiting for input can call `set_idle_mode(true)`,
+/// so that `run` blocks waiting for events while there is nothing to draw or animate.
+///
+/// ## Handling Errors
+/// A `Widget` that fails to draw, ie. because its font or image could not be loaded, is drawn as a
+/// placeholder instead, and the application keeps running.  Use `on_error` to be told about these
+/// errors, and about errors that stop `run`.  Call `try_run` instead of `run` to receive an error
+/// if the `Window` could not be set up.  If no callback is set, errors are logged.
+///
+/// ## Binding Data to Widgets
+/// Store the state of your application in `Observable` values, and call `bind` to connect each
+/// one to a configuration value of a `Widget`, such as its text or selected state.  The `Widget`
+/// is then updated whenever the `Observable` changes, and with a two-way binding, the `Observable`
+/// is updated when the user changes the `Widget`.  Use `Observable::subscribe` to react to
+/// changes, rather than reading and writing `Widget`s from callbacks.
+///
+/// ## Signals
+/// `Widget`s and application code can communicate without referring to each other through named
+/// signals.  Declare a `Signal` with the type of its payload, `emit` it through an `EngineHandle`,
+/// and `connect` slots to it, or use `connect_widget` to have a slot receive a `Widget`.  Signals
+/// are delivered once the callback that emitted them has returned, so no `Widget` is borrowed while
+/// the slots are called.
+///
+/// ## Controlling the Engine from Callbacks
+/// Call `get_handle()` to obtain an `EngineHandle`, and move a clone of it into any callback that
+/// needs to quit the application, change the window title or fullscreen state, or force a repaint.
+/// Use `on_quit_requested` to confirm or cancel a shutdown request.
+///
+/// ## Updating Widgets from Other Threads
+/// Call `get_sender()` to obtain a `WidgetSender`, which can be moved into worker threads.  It posts
+/// closures or configuration changes to `Widget`s by name or ID, which the `Engine` applies on the
+/// UI thread at the start of each `tick`.  In idle mode, posted messages are picked up when the
+/// `Engine` next wakes up, which is at most a quarter of a second later.
+///
+/// ## Embedding in an Existing Loop
+/// If your application already has its own event loop and canvas, do not call `run`.  Instead,
+/// pass each SDL2 event to `handle_event(&event)`, then call `tick()` and `draw(&mut canvas)` once
+/// per frame, before presenting the canvas yourself.  Call `apply_window_requests(&mut window)` to
+/// honor `Window` changes that were requested through an `EngineHandle`.  Drawing through a
+/// `TextureCanvas` keeps the textures of images between frames, as `run` does.
+///
 /// That's all there is to it.  If you want to see more interactions on how the `Engine` is used in
 /// an application, check out the demo test code, and look at `rust-pushrod-chassis`.
 impl Engine {
     /// Creates a new `Engine` object.
     pub fn new() -> Self {
+        let (message_sender, message_receiver) = channel();
+
         Self {
             cache: WidgetCache::new(),
             current_widget_id: 0,
+            captured_widget_id: 0,
+            captured_button: 0,
+            captured_inside: false,
+            drag_origin: vec![0, 0],
+            dragging: false,
+            drag_payload: None,
+            drop_target_id: 0,
+            mouse_point: vec![0, 0],
+            running: true,
+            frame_rate: DEFAULT_FRAME_RATE,
+            idle_mode: false,
+            handle: EngineHandle::default(),
+            on_quit_requested: None,
+            on_error: None,
+            signal_slots: SignalSlots::new(),
+            message_sender,
+            message_receiver,
         }
     }
 
@@ -68,101 +292,712 @@ impl Engine {
     }
 
     /// Adds a widget to the display list.  Widgets are rendered in the order in which they were
-    /// created in the display list.
-    pub fn add_widget(&mut self, widget: Box<dyn Widget>, widget_name: String) -> i32 {
+    /// created in the display list.  Returns the `WidgetId` of the new `Widget`.
+    pub fn add_widget(&mut self, widget: Box<dyn Widget>, widget_name: String) -> WidgetId {
         self.cache.add_widget(widget, widget_name)
     }
 
-    /// Main application run loop, controls interaction between the user and the application.
-    pub fn run(&mut self, sdl: Sdl, window: Window) {
-        let mut canvas = window.into_canvas().software().build().unwrap();
+    /// Adds a widget to the overlay layer of the display list, which is drawn above all `Widget`s
+    /// added with `add_widget` or `add_child`, regardless of when they were added.  Returns the
+    /// `WidgetId` of the new `Widget`.
+    pub fn add_overlay(&mut self, widget: Box<dyn Widget>, widget_name: String) -> WidgetId {
+        self.cache.add_overlay(widget, widget_name)
+    }
 
-        canvas.clear();
+    /// Moves the `Widget` specified by `widget_id` one step up among its siblings.  Returns an
+    /// error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn raise(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        self.cache.raise(widget_id)
+    }
+
+    /// Moves the `Widget` specified by `widget_id` one step down among its siblings.  Returns an
+    /// error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn lower(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        self.cache.lower(widget_id)
+    }
+
+    /// Moves the `Widget` specified by `widget_id` above all of its siblings in the same layer.
+    /// Returns an error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn bring_to_front(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        self.cache.bring_to_front(widget_id)
+    }
+
+    /// Moves the `Widget` specified by `widget_id` below all of its siblings in the same layer.
+    /// Returns an error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn send_to_back(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        self.cache.send_to_back(widget_id)
+    }
+
+    /// Removes the `Widget` specified by `widget_id` from the display list, along with all of its
+    /// children.  If the mouse is captured by a removed `Widget`, the capture and any drag in
+    /// progress are cancelled.  Returns an error if the `Widget` does not exist, or if it is the
+    /// top-level `Widget`.
+    pub fn remove_widget(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        let current_id = self.cache.get_id_of(self.current_widget_id);
+        let captured_id = self.cache.get_id_of(self.captured_widget_id);
+        let drop_target_id = self.cache.get_id_of(self.drop_target_id);
+
+        self.cache.remove_widget(widget_id)?;
+
+        let resolve = |cache: &WidgetCache, id: Option<WidgetId>| {
+            id.and_then(|id| cache.get_widget_index(id)).unwrap_or(0)
+        };
+
+        self.current_widget_id = resolve(&self.cache, current_id);
+        self.captured_widget_id = resolve(&self.cache, captured_id);
+        self.drop_target_id = resolve(&self.cache, drop_target_id);
+
+        if self.captured_widget_id == 0 {
+            self.dragging = false;
+            self.drag_payload = None;
+            self.drop_target_id = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the number of frames per second that `run` targets.  Each frame sleeps only for the
+    /// time remaining after events have been handled and `Widget`s have been drawn.  A value of `0`
+    /// is treated as `1`.  Defaults to `DEFAULT_FRAME_RATE`.
+    pub fn set_frame_rate(&mut self, frame_rate: u32) {
+        self.frame_rate = frame_rate.max(1);
+    }
+
+    /// Returns the number of frames per second that `run` targets.
+    pub fn get_frame_rate(&self) -> u32 {
+        self.frame_rate
+    }
+
+    /// Enables or disables idle mode.  When enabled, `run` blocks waiting for the next event
+    /// whenever no `Widget` is invalidated and no `Widget` needs a `tick`, instead of waking up
+    /// every frame.  Disabled by default.
+    pub fn set_idle_mode(&mut self, idle_mode: bool) {
+        self.idle_mode = idle_mode;
+    }
+
+    /// Indicates whether or not idle mode is enabled.
+    pub fn is_idle_mode(&self) -> bool {
+        self.idle_mode
+    }
+
+    /// Adds a widget to the display list as a child of the `Widget` specified by `parent_id`.  The
+    /// origin of the child is relative to its parent, and the child is clipped to the drawing area
+    /// of its parent.  Returns an error if the parent does not exist.
+    pub fn add_child(
+        &mut self,
+        parent_id: WidgetId,
+        widget: Box<dyn Widget>,
+        widget_name: String,
+    ) -> Result<WidgetId, String> {
+        self.cache.add_child(parent_id, widget, widget_name)
+    }
+
+    /// Assigns keyboard focus to the `Widget` specified by `widget_id`.  This is the same ID that is
+    /// returned from `add_widget`.  Stale IDs are ignored.
+    pub fn set_focus(&mut self, widget_id: WidgetId) {
+        if let Some(index) = self.cache.get_widget_index(widget_id) {
+            self.cache.set_focus(index);
+        }
+    }
+
+    /// Renders the entire display list to a new `Surface` of the given `width` and `height`,
+    /// without the need for a `Window` or a display.  All `Widget`s are drawn, regardless of their
+    /// invalidation state, which is left untouched, so this can be called while `run` is active
+    /// without affecting what is drawn to the `Window`.  The resulting `Surface` can be saved (ie.
+    /// `surface.save_bmp(path)`), or inspected directly.
+    pub fn render_to_surface(
+        &mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<Surface<'static>, String> {
+        let surface = Surface::new(width, height, PixelFormatEnum::RGBA8888)?;
+        let mut canvas = Canvas::from_surface(surface)?;
+
+        self.cache.draw_all(&mut canvas);
+        self.report_draw_errors();
         canvas.present();
 
-        let mut event_pump = sdl.event_pump().unwrap();
-
-        'running: loop {
-            for event in event_pump.poll_iter() {
-                match event {
-                    Event::MouseButtonDown {
-                        mouse_btn, clicks, ..
-                    } => {
-                        self.cache.button_clicked(
-                            self.current_widget_id,
-                            mouse_btn as u8,
-                            clicks,
-                            true,
-                        );
-                    }
+        Ok(canvas.into_surface())
+    }
 
-                    Event::MouseButtonUp {
-                        mouse_btn, clicks, ..
-                    } => {
-                        self.cache
-                            .button_clicked(-1, mouse_btn as u8, clicks, false);
-                    }
+    /// Binds the configuration value `key` of the `Widget` specified by `widget_id` to the
+    /// `observable`, so that the `Widget` is updated and redrawn whenever the `observable` changes.
+    /// With `BindingMode::TwoWay`, changes that the `Widget` makes to the value, such as a toggle
+    /// changing `CONFIG_SELECTED_STATE` when clicked, are copied back to the `observable`.  Changes
+    /// are copied on every `tick`.  Returns an error if the `Widget` does not exist.
+    pub fn bind<T: ConfigValue + Clone + PartialEq + 'static>(
+        &mut self,
+        widget_id: WidgetId,
+        key: ConfigKey<T>,
+        observable: &Observable<T>,
+        mode: BindingMode,
+    ) -> Result<(), String> {
+        self.cache.bind(widget_id, key, observable, mode)
+    }
 
-                    Event::MouseMotion { x, y, .. } => {
-                        let cur_widget_id = self.current_widget_id;
+    /// Connects a `callback` to `signal`, which is called with the payload of the signal, and the
+    /// list of `WidgetContainer`s, whenever it is emitted.  Returns a `SlotId`, which can be passed
+    /// to `disconnect` to remove the callback.
+    pub fn connect<T, F>(&mut self, signal: Signal<T>, callback: F) -> SlotId
+    where
+        T: 'static,
+        F: FnMut(&T, &[WidgetContainer]) + 'static,
+    {
+        self.signal_slots.connect(signal, callback)
+    }
 
-                        self.current_widget_id = self.cache.find_widget(x, y);
+    /// Connects a `callback` to `signal` on behalf of the `Widget` specified by `widget_id`.  The
+    /// callback is called with that `Widget`, the payload of the signal, and the list of
+    /// `WidgetContainer`s, and the `Widget` is invalidated afterwards.  The slot is removed when the
+    /// `Widget` is removed.
+    pub fn connect_widget<T, F>(
+        &mut self,
+        widget_id: WidgetId,
+        signal: Signal<T>,
+        callback: F,
+    ) -> SlotId
+    where
+        T: 'static,
+        F: FnMut(&mut dyn Widget, &T, &[WidgetContainer]) + 'static,
+    {
+        self.signal_slots
+            .connect_widget(widget_id, signal, callback)
+    }
 
-                        if cur_widget_id != self.current_widget_id {
-                            self.cache.mouse_exited(cur_widget_id);
-                            self.cache.mouse_entered(self.current_widget_id);
-                        }
+    /// Removes a slot that was connected with `connect` or `connect_widget`.
+    pub fn disconnect(&mut self, slot_id: SlotId) {
+        self.signal_slots.disconnect(slot_id);
+    }
 
-                        self.cache.mouse_moved(self.current_widget_id, vec![x, y]);
-                    }
+    /// Emits `signal` with the `payload` given, in the same way as `EngineHandle::emit`.  The
+    /// signal is delivered the next time the `Engine` handles an event or `tick` is called.
+    pub fn emit<T: 'static>(&self, signal: Signal<T>, payload: T) {
+        self.handle.emit(signal, payload);
+    }
+
+    /// Returns a mutable reference to the `WidgetCache` that stores the display list, so that
+    /// `Widget`s can be accessed after they have been added to the `Engine`.
+    pub fn get_widget_cache(&mut self) -> &mut WidgetCache {
+        &mut self.cache
+    }
+
+    /// Returns the ID of the `Widget` that has captured the mouse, or `0` if the mouse is not
+    /// captured.
+    pub fn get_captured_widget_id(&self) -> i32 {
+        self.captured_widget_id
+    }
+
+    /// Returns an `EngineHandle`, which can be moved into callbacks to quit the application,
+    /// change the state of the `Window`, or force a repaint of all `Widget`s.
+    pub fn get_handle(&self) -> EngineHandle {
+        self.handle.clone()
+    }
+
+    /// Returns a `WidgetSender`, which can be moved into worker threads to post updates to
+    /// `Widget`s.  Posted messages are applied on the UI thread at the start of each `tick`.
+    pub fn get_sender(&self) -> WidgetSender {
+        WidgetSender::new(self.message_sender.clone())
+    }
+
+    /// Assigns the callback closure that will be used when the `Engine` is asked to quit, either
+    /// by the window system, or through `EngineHandle::quit`.  Return `true` from the callback to
+    /// allow the `Engine` to stop, or `false` to cancel the shutdown (ie. when there is unsaved
+    /// work).  If no callback is set, the `Engine` always stops.
+    pub fn on_quit_requested<F>(&mut self, callback: F)
+    where
+        F: FnMut(&[WidgetContainer]) -> bool + 'static,
+    {
+        self.on_quit_requested = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when the `Engine` encounters an error: when
+    /// a `Widget` fails to draw, or when `run` cannot set up the `Window`.  The callback receives
+    /// the `WidgetId` of the `Widget` that failed, if any, and the error.  Each failing `Widget` is
+    /// reported once per draw in which it failed.
+    pub fn on_error<F>(&mut self, callback: F)
+    where
+        F: FnMut(Option<WidgetId>, &PushrodError) + 'static,
+    {
+        self.on_error = Some(Box::new(callback));
+    }
+
+    /// Applies any pending `Window` changes that were requested through an `EngineHandle`.  This
+    /// is called automatically by `run`, but must be called by applications that drive their own
+    /// loop, as the `Engine` does not own the `Window` in that case.
+    pub fn apply_window_requests(&mut self, window: &mut Window) {
+        let mut requests = self.handle.requests.borrow_mut();
+
+        if let Some(title) = requests.window_title.take() {
+            if let Err(e) = window.set_title(&title) {
+                warn!(target: "pushrod::events", "Unable to set window title: {}", e);
+            }
+        }
+
+        if let Some(fullscreen) = requests.fullscreen.take() {
+            let fullscreen_type = if fullscreen {
+                FullscreenType::Desktop
+            } else {
+                FullscreenType::Off
+            };
+
+            if let Err(e) = window.set_fullscreen(fullscreen_type) {
+                warn!(target: "pushrod::events", "Unable to set fullscreen mode: {}", e);
+            }
+        }
+    }
+
+    /// Indicates whether or not the `Engine` is still running.  This is set to `false` once a
+    /// `Quit` event has been handled.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
 
-                    Event::MouseWheel { x, y, .. } => {
-                        self.cache
-                            .mouse_scrolled(self.current_widget_id, vec![x, y]);
+    /// Handles a single SDL2 `Event`, dispatching it to the `Widget`s in the display list.  This
+    /// is called by `run` for every event that it receives, but it can also be called directly
+    /// when the application drives its own event loop.  A `Quit` event stops the `Engine`, which
+    /// can be checked through `is_running`.  Dropped files are delivered to the `Widget` under the
+    /// last mouse position that was received in a `MouseMotion` event.
+    pub fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::MouseButtonDown {
+                mouse_btn,
+                clicks,
+                x,
+                y,
+                ..
+            } => {
+                let button = *mouse_btn as u8;
+
+                if self.captured_widget_id == 0 {
+                    self.captured_widget_id = self.current_widget_id;
+                    self.captured_button = button;
+                    self.captured_inside = true;
+                    self.drag_origin = vec![*x, *y];
+                    self.dragging = false;
+                    self.cache.set_focus(self.current_widget_id);
+                }
+
+                self.cache
+                    .button_clicked(self.current_widget_id, button, *clicks, true);
+            }
+
+            Event::MouseButtonUp {
+                mouse_btn,
+                clicks,
+                x,
+                y,
+                ..
+            } => {
+                let button = *mouse_btn as u8;
+
+                self.cache
+                    .button_clicked(self.current_widget_id, button, *clicks, false);
+
+                if self.captured_widget_id != 0 && button == self.captured_button {
+                    self.release_capture(*x, *y);
+                }
+            }
+
+            Event::MouseMotion { x, y, .. } => {
+                self.mouse_point = vec![*x, *y];
+
+                if self.captured_widget_id != 0 {
+                    let captured_id = self.captured_widget_id;
+                    let inside = self.cache.is_point_in_widget(captured_id, *x, *y);
+
+                    if inside != self.captured_inside {
+                        self.captured_inside = inside;
+
+                        if inside {
+                            self.cache.mouse_entered(captured_id);
+                        } else {
+                            self.cache.mouse_exited(captured_id);
+                        }
                     }
 
-                    Event::Quit { .. } => {
-                        //                        let buttons: Vec<_> = vec![
-                        //                            ButtonData {
-                        //                                flags: MessageBoxButtonFlag::RETURNKEY_DEFAULT,
-                        //                                button_id: 1,
-                        //                                text: "Yes",
-                        //                            },
-                        //                            ButtonData {
-                        //                                flags: MessageBoxButtonFlag::ESCAPEKEY_DEFAULT,
-                        //                                button_id: 2,
-                        //                                text: "No",
-                        //                            },
-                        //                        ];
-                        //
-                        //                        let res = show_message_box(
-                        //                            MessageBoxFlag::WARNING,
-                        //                            buttons.as_slice(),
-                        //                            "Quit",
-                        //                            "Are you sure?",
-                        //                            None,
-                        //                            None,
-                        //                        )
-                        //                        .unwrap();
-                        //
-                        //                        if let ClickedButton::CustomButton(x) = res {
-                        //                            if x.button_id == 1 {
-                        break 'running;
-                        //                            }
-                        //                        }
+                    if !self.dragging {
+                        self.dragging = true;
+                        self.cache.drag_start(captured_id, self.drag_origin.clone());
+                        self.drag_payload = self.cache.drag_payload(captured_id);
                     }
 
-                    _ => {
-                        // Send event through to widget.
-                        eprintln!("Event: {:?}", event);
+                    self.cache.drag(captured_id, vec![*x, *y]);
+                    self.update_drop_target(*x, *y);
+                    self.cache.mouse_moved(captured_id, vec![*x, *y]);
+                } else {
+                    let cur_widget_id = self.current_widget_id;
+
+                    self.current_widget_id = self.cache.find_widget(*x, *y);
+
+                    if cur_widget_id != self.current_widget_id {
+                        self.cache.mouse_exited(cur_widget_id);
+                        self.cache.mouse_entered(self.current_widget_id);
                     }
+
+                    self.cache.mouse_moved(self.current_widget_id, vec![*x, *y]);
                 }
             }
 
-            self.cache.tick();
-            self.cache.draw_loop(&mut canvas);
+            Event::MouseWheel { x, y, .. } => {
+                self.cache
+                    .mouse_scrolled(self.current_widget_id, vec![*x, *y]);
+            }
+
+            Event::KeyDown {
+                keycode: Some(keycode),
+                keymod,
+                ..
+            } => {
+                self.cache.key_pressed(*keycode, *keymod);
+            }
+
+            Event::KeyUp {
+                keycode: Some(keycode),
+                keymod,
+                ..
+            } => {
+                self.cache.key_released(*keycode, *keymod);
+            }
+
+            Event::TextInput { text, .. } => {
+                self.cache.text_input(text.clone());
+            }
+
+            Event::TextEditing {
+                text,
+                start,
+                length,
+                ..
+            } => {
+                self.cache.text_edited(text.clone(), *start, *length);
+            }
+
+            Event::DropFile { filename, .. } => {
+                let x = self.mouse_point[0];
+                let y = self.mouse_point[1];
+                let target_id = self.cache.find_widget(x, y);
+
+                if !self
+                    .cache
+                    .dropped(target_id, DragPayload::File(filename.clone()), vec![x, y])
+                {
+                    debug!(
+                        target: "pushrod::events",
+                        "Dropped file not accepted: widget={} file={}",
+                        target_id,
+                        filename
+                    );
+                }
+            }
+
+            Event::Unknown { type_, .. } if *type_ == SDL_EventType::SDL_DROPTEXT as u32 => {
+                debug!(
+                    target: "pushrod::events",
+                    "Dropped text is not supported, and was ignored."
+                );
+            }
+
+            Event::Quit { .. } => {
+                self.handle.quit();
+            }
+
+            _ => {
+                // Send event through to widget.
+                trace!(target: "pushrod::events", "Unhandled event: {:?}", event);
+            }
+        }
+
+        self.process_requests();
+    }
+
+    /// Applies any messages posted through a `WidgetSender`, then calls the `tick` method on all
+    /// `Widget`s in the display list.  This should be called once per frame, after events have
+    /// been handled, and before drawing.
+    pub fn tick(&mut self) {
+        while let Ok(message) = self.message_receiver.try_recv() {
+            self.cache.apply_message(message);
+        }
+
+        self.cache.tick();
+        self.cache.sync_bindings();
+        self.process_requests();
+    }
+
+    /// Repaints the `Widget`s in the display list that have changed since the last call to the
+    /// canvas, in the same way as `run`.  Returns `true` if anything was drawn.  The canvas is not
+    /// presented, so this can be used to draw the `Widget`s as an overlay on top of a frame that
+    /// the application has drawn itself, before the application calls `present`.  If the
+    /// application clears or draws over the `Widget`s, call `invalidate_all` on the
+    /// `get_widget_cache` first, so that they are all repainted.  To show the application's
+    /// drawing behind the `Widget`s, hide the top-level `BaseWidget` (ID `0`) through
+    /// `get_widget_cache`.
+    pub fn draw(&mut self, c: &mut dyn RenderCanvas) -> bool {
+        let drawn = self.cache.draw_loop(c);
+
+        self.report_draw_errors();
+        drawn
+    }
+
+    /// Returns the `ResourceCache` that `Widget`s load their fonts and images from.  Call `clear`
+    /// on it to reload resources that have changed on disk.
+    pub fn get_resources(&mut self) -> &mut ResourceCache {
+        self.cache.get_resources()
+    }
+
+    /// Returns the areas of the screen, in `Window` coordinates, that were redrawn by the last
+    /// draw.  Applications that present the canvas themselves can use this to update only the
+    /// parts of the `Window` that changed.
+    pub fn get_damage(&self) -> &[Rect] {
+        self.cache.get_damage()
+    }
+
+    /// Main application run loop, controls interaction between the user and the application.
+    /// This takes ownership of the `Window`, and does not return until a `Quit` event is received.
+    /// Applications that run their own loop should call `handle_event`, `tick` and `draw` instead.
+    /// If the `Window` cannot be set up, the error is reported to the `on_error` callback, and
+    /// this returns immediately.
+    pub fn run(&mut self, sdl: Sdl, window: Window) {
+        if let Err(e) = self.try_run(sdl, window) {
+            self.report_error(None, &e);
+        }
+    }
+
+    /// Runs the application in the same way as `run`, but returns an error if the canvas or the
+    /// event pump for the `Window` could not be created.
+    pub fn try_run(&mut self, sdl: Sdl, window: Window) -> Result<(), PushrodError> {
+        let mut canvas = window
+            .into_canvas()
+            .software()
+            .build()
+            .map_err(|e| PushrodError::Init(e.to_string()))?;
+
+        canvas.clear();
+        canvas.present();
+
+        let texture_creator = canvas.texture_creator();
+        let mut textures = TextureCache::new(&texture_creator);
+        let mut event_pump = sdl.event_pump().map_err(PushrodError::Init)?;
+
+        self.running = true;
+
+        while self.running {
+            let frame_start = Instant::now();
+
+            if self.idle_mode && !self.cache.is_invalidated() && !self.cache.needs_tick() {
+                if let Some(event) = event_pump.wait_event_timeout(IDLE_WAIT_TIMEOUT_MS) {
+                    self.handle_pumped_event(&event_pump, &event);
+                }
+            }
+
+            let events: Vec<Event> = event_pump.poll_iter().collect();
+
+            for event in events {
+                self.handle_pumped_event(&event_pump, &event);
+            }
+
+            self.tick();
+            self.apply_window_requests(canvas.window_mut());
+
+            let drawn = self
+                .cache
+                .draw_loop(&mut TextureCanvas::new(&mut canvas, &mut textures));
+
+            if drawn {
+                self.report_draw_errors();
+
+                trace!(target: "pushrod::draw", "Presenting canvas.");
+                canvas.present();
+            }
+
+            let frame_time = Duration::from_nanos(1_000_000_000u64 / u64::from(self.frame_rate));
+
+            if let Some(remaining) = frame_time.checked_sub(frame_start.elapsed()) {
+                ::std::thread::sleep(remaining);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Engine {
+    /// Handles an `Event` that was received from `event_pump`.  No motion events are received
+    /// while a file is dragged onto the `Window` from another application, so the mouse position
+    /// is read from `event_pump` before a dropped file is handled.
+    fn handle_pumped_event(&mut self, event_pump: &EventPump, event: &Event) {
+        if let Event::DropFile { .. } = event {
+            let mouse_state = event_pump.mouse_state();
+
+            self.mouse_point = vec![mouse_state.x(), mouse_state.y()];
+        }
+
+        self.handle_event(event);
+    }
+
+    /// Moves the drop target of the current drag and drop operation to the `Widget` under `x` and
+    /// `y`, if it accepts the payload, sending `drop_exited` and `drop_entered` as it changes.
+    fn update_drop_target(&mut self, x: i32, y: i32) {
+        let payload = match &self.drag_payload {
+            Some(payload) => payload.clone(),
+            None => return,
+        };
+
+        let hover_id = self.cache.find_widget(x, y);
+        let target_id =
+            if hover_id != self.captured_widget_id && self.cache.accepts_drop(hover_id, &payload) {
+                hover_id
+            } else {
+                0
+            };
+
+        if target_id != self.drop_target_id {
+            if self.drop_target_id != 0 {
+                self.cache.drop_exited(self.drop_target_id);
+            }
+
+            if target_id != 0 {
+                self.cache.drop_entered(target_id);
+            }
+
+            self.drop_target_id = target_id;
+        }
+    }
+
+    /// Releases the mouse capture when the capturing button is released at `x` and `y`, ending
+    /// any drag, and re-targets the mouse to the `Widget` now under the pointer.
+    fn release_capture(&mut self, x: i32, y: i32) {
+        let captured_id = self.captured_widget_id;
+
+        self.captured_widget_id = 0;
+
+        if self.dragging {
+            self.dragging = false;
+            self.cache.drag_end(captured_id, vec![x, y]);
+        }
+
+        if let Some(payload) = self.drag_payload.take() {
+            let target_id = self.drop_target_id;
+
+            if target_id != 0 {
+                self.drop_target_id = 0;
+                self.cache.drop_exited(target_id);
+                self.cache.dropped(target_id, payload, vec![x, y]);
+            }
+        }
+
+        self.current_widget_id = self.cache.find_widget(x, y);
+
+        if !self.captured_inside {
+            self.cache.mouse_entered(self.current_widget_id);
+        } else if self.current_widget_id != captured_id {
+            self.cache.mouse_exited(captured_id);
+            self.cache.mouse_entered(self.current_widget_id);
+        }
+    }
+
+    /// Applies the quit, repaint and removal requests made through the `EngineHandle`.
+    fn process_requests(&mut self) {
+        self.deliver_signals();
+
+        let (quit, repaint, removals, restacks) = {
+            let mut requests = self.handle.requests.borrow_mut();
+            let quit = requests.quit;
+            let repaint = requests.repaint;
+            let removals = std::mem::take(&mut requests.removals);
+            let restacks = std::mem::take(&mut requests.restacks);
+
+            requests.quit = false;
+            requests.repaint = false;
+
+            (quit, repaint, removals, restacks)
+        };
+
+        for widget_id in removals {
+            if let Err(e) = self.remove_widget(widget_id) {
+                debug!(target: "pushrod::cache", "Unable to remove widget: {}", e);
+            }
+        }
+
+        for (widget_id, to_front) in restacks {
+            let result = if to_front {
+                self.cache.bring_to_front(widget_id)
+            } else {
+                self.cache.send_to_back(widget_id)
+            };
+
+            if let Err(e) = result {
+                debug!(target: "pushrod::cache", "Unable to restack widget: {}", e);
+            }
+        }
+
+        if repaint {
+            self.cache.invalidate_all();
+        }
+
+        if quit && self.confirm_quit() {
+            self.running = false;
+        }
+    }
+
+    /// Delivers the signals that were emitted through an `EngineHandle`.  Signals that slots emit
+    /// are delivered in turn, up to `MAX_SIGNAL_ROUNDS` times.
+    fn deliver_signals(&mut self) {
+        for _ in 0..MAX_SIGNAL_ROUNDS {
+            let signals = std::mem::take(&mut self.handle.requests.borrow_mut().signals);
+
+            if signals.is_empty() {
+                return;
+            }
+
+            for signal in signals {
+                self.signal_slots.deliver(signal, self.cache.get_widgets());
+            }
+        }
+
+        warn!(
+            target: "pushrod::events",
+            "Signals are still being emitted after {} rounds, delivering the rest later.",
+            MAX_SIGNAL_ROUNDS
+        );
+    }
+
+    /// Consults the `on_quit_requested` callback, returning `true` if the `Engine` may stop.
+    fn confirm_quit(&mut self) -> bool {
+        match self.on_quit_requested.take() {
+            Some(mut cb) => {
+                let result = cb(self.cache.get_widgets());
+
+                self.on_quit_requested = Some(cb);
+                result
+            }
+
+            None => true,
+        }
+    }
+
+    /// Reports the errors of `Widget`s that failed to draw during the last draw to the `on_error`
+    /// callback.
+    fn report_draw_errors(&mut self) {
+        for (widget_id, e) in self.cache.take_errors() {
+            self.report_error(Some(widget_id), &e);
+        }
+    }
+
+    /// Reports an error to the `on_error` callback, or logs it if no callback is set.
+    fn report_error(&mut self, widget_id: Option<WidgetId>, e: &PushrodError) {
+        match self.on_error.take() {
+            Some(mut cb) => {
+                cb(widget_id, e);
+
+                self.on_error = Some(cb);
+            }
 
-            ::std::thread::sleep(Duration::new(0, 1_000_000_000u32 / 60));
+            None => error!(
+                target: "pushrod::draw",
+                "Error: widget_id={:?} error={}",
+                widget_id,
+                e
+            ),
         }
     }
 }
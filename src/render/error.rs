@@ -0,0 +1,55 @@
+// Pushrod Rendering Library
+// Error Reporting
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+/// This is an error that is reported by Pushrod when loading resources, drawing `Widget`s, or
+/// running the `Engine`.  Errors that occur while drawing a `Widget` do not stop the application:
+/// the `Widget` is drawn as a placeholder, and the error is reported to the `Engine`'s
+/// `on_error` callback.
+#[derive(Clone, Debug)]
+pub enum PushrodError {
+    /// A font could not be loaded.  Contains the name or path of the font, and the reason.
+    FontLoad(String, String),
+
+    /// An image could not be loaded.  Contains the path of the image, and the reason.
+    ImageLoad(String, String),
+
+    /// Rendering text, or drawing to a canvas, failed.  Contains the reason.
+    Render(String),
+
+    /// The `Engine` could not initialize the canvas or event handling for a `Window`.  Contains
+    /// the reason.
+    Init(String),
+}
+
+/// This is the `Display` implementation of the `PushrodError`.
+impl fmt::Display for PushrodError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PushrodError::FontLoad(name, reason) => {
+                write!(f, "Unable to load font {}: {}", name, reason)
+            }
+            PushrodError::ImageLoad(path, reason) => {
+                write!(f, "Unable to load image {}: {}", path, reason)
+            }
+            PushrodError::Render(reason) => write!(f, "Unable to render: {}", reason),
+            PushrodError::Init(reason) => write!(f, "Unable to initialize: {}", reason),
+        }
+    }
+}
+
+impl Error for PushrodError {}
@@ -0,0 +1,140 @@
+// Pushrod Rendering Library
+// Cross-Thread Widget Messages
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget::Widget;
+use crate::render::widget_cache::{WidgetContainer, WidgetId};
+use crate::render::widget_config::{Config, ConfigKey, ConfigKeyId, ConfigValue, CONFIG_SIZE};
+use log::warn;
+use std::sync::mpsc::Sender;
+
+/// This identifies the `Widget` that a `WidgetMessage` is applied to.
+#[derive(Clone, Debug)]
+pub enum WidgetTarget {
+    /// Targets a `Widget` by the `WidgetId` that was returned by `add_widget`.
+    Id(WidgetId),
+
+    /// Targets a `Widget` by the name it was added with.
+    Name(String),
+}
+
+/// This is the type of closure that can be sent to a `Widget` from another thread.  It is called
+/// on the UI thread with the target `Widget`, and the list of `WidgetContainer`s in the cache.
+pub type WidgetUpdateFn = Box<dyn FnOnce(&mut dyn Widget, &[WidgetContainer]) + Send>;
+
+/// This is a message that is posted to the `Engine` through a `WidgetSender`.
+pub enum WidgetMessage {
+    /// Runs a closure against the target `Widget`.
+    Update(WidgetTarget, WidgetUpdateFn),
+
+    /// Sets a configuration value on the target `Widget`, using the same setters that are used
+    /// by applications, so `on_config_changed` is called.
+    Config(WidgetTarget, u8, Config),
+
+    /// Sets the configuration value identified by a `ConfigKeyId` on the target `Widget`, through
+    /// `Widget::set_config_value`.
+    Value(WidgetTarget, ConfigKeyId, Config),
+}
+
+/// This is a thread-safe handle that is used to post `WidgetMessage`s to the `Engine`, which can be
+/// obtained by calling `Engine::get_sender`.  It can be cloned and moved into worker threads.  The
+/// `Engine` applies all pending messages on the UI thread at the start of each `tick`, and marks
+/// the target `Widget` as invalidated, so it is redrawn.
+#[derive(Clone)]
+pub struct WidgetSender {
+    sender: Sender<WidgetMessage>,
+}
+
+/// This is the implementation of the `WidgetSender`.
+impl WidgetSender {
+    /// Creates a new `WidgetSender` that posts messages to `sender`.
+    pub fn new(sender: Sender<WidgetMessage>) -> Self {
+        Self { sender }
+    }
+
+    /// Posts a `WidgetMessage` to the `Engine`.  Returns an error if the `Engine` no longer
+    /// exists.
+    pub fn send(&self, message: WidgetMessage) -> Result<(), String> {
+        self.sender
+            .send(message)
+            .map_err(|_| String::from("Engine is no longer receiving messages"))
+    }
+
+    /// Posts a closure that is run against the `target` `Widget` on the UI thread.
+    pub fn update<F>(&self, target: WidgetTarget, f: F) -> Result<(), String>
+    where
+        F: FnOnce(&mut dyn Widget, &[WidgetContainer]) + Send + 'static,
+    {
+        self.send(WidgetMessage::Update(target, Box::new(f)))
+    }
+
+    /// Posts a configuration change for the `target` `Widget`.
+    pub fn set_config(&self, target: WidgetTarget, key: u8, value: Config) -> Result<(), String> {
+        self.send(WidgetMessage::Config(target, key, value))
+    }
+
+    /// Posts a change to the value of a typed configuration `key` for the `target` `Widget`.
+    pub fn set_value<T: ConfigValue>(
+        &self,
+        target: WidgetTarget,
+        key: ConfigKey<T>,
+        value: T,
+    ) -> Result<(), String> {
+        self.send(WidgetMessage::Value(target, key.id(), value.into_config()))
+    }
+}
+
+/// Applies a configuration value to a `Widget` through its setters.  `Points` and `Size` values
+/// must contain two values, otherwise they are dropped with a warning.
+pub(crate) fn apply_config<W: Widget + ?Sized>(widget: &mut W, key: u8, value: Config) {
+    let len = match &value {
+        Config::Points(points) => points.len(),
+        Config::Size(size) => size.len(),
+        _ => 2,
+    };
+
+    if len < 2 {
+        warn!(
+            target: "pushrod::events",
+            "{} value for key {} needs 2 values, but has {}, and was dropped.",
+            value.type_name(),
+            key,
+            len
+        );
+        return;
+    }
+
+    match value {
+        Config::Points(points) => widget.set_point(key, points[0], points[1]),
+        Config::Size(size) => {
+            if key == CONFIG_SIZE {
+                widget.set_size(size);
+            } else {
+                widget.get_config().set_size(key, size[0], size[1]);
+                widget.on_config_changed(key, Config::Size(size));
+            }
+        }
+        Config::Color(color) => widget.set_color(key, color),
+        Config::Numeric(value) => widget.set_numeric(key, value),
+        Config::Text(text) => widget.set_text(key, text),
+        Config::Toggle(flag) => widget.set_toggle(key, flag),
+        Config::CompassPosition(position) => widget.set_compass(key, position),
+        Config::Float(value) => widget.set_float(key, value),
+        Config::Rect(rect) => widget.set_rect(key, rect),
+        Config::Insets(insets) => widget.set_insets(key, insets),
+        Config::StringList(list) => widget.set_string_list(key, list),
+        Config::Font(font) => widget.set_font(key, font),
+        Config::Image(image) => widget.set_image(key, image),
+    }
+}
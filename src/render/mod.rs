@@ -23,6 +23,37 @@ pub type Size = Vec<u32>;
 /// of `Widget`s in a `Window`.  This is the main event loop.
 pub mod engine;
 
+/// This is the `RenderCanvas` abstraction that `Widget`s draw to, allowing them to be drawn to a
+/// `Window` on the screen, or to an offscreen `Surface` in memory.
+pub mod canvas;
+
+/// This is the payload type of drag and drop operations between `Widget`s, and of files dropped
+/// onto the `Window`.
+pub mod drag_drop;
+
+/// This is the `WidgetSender`, which posts configuration changes to `Widget`s as `WidgetMessage`s,
+/// including from other threads.
+pub mod messages;
+
+/// This is the cache of fonts and images that `Widget`s share while drawing.
+pub mod resources;
+
+/// This is the `Observable` value type, which binds an application's model to the configuration
+/// of `Widget`s.
+pub mod observable;
+
+/// This is the signal and slot mechanism, which delivers named signals between `Widget`s and
+/// application code.
+pub mod signals;
+
+/// This is the error type that is reported when resources cannot be loaded, or `Widget`s cannot
+/// be drawn.
+pub mod error;
+
+/// This is a grid-based index of `Widget` areas, which the `WidgetCache` uses to find the `Widget`
+/// at a point on the screen.
+pub mod spatial_index;
+
 /// This is the `Callbacks` mechanism for each `Widget`, providing a way to perform a function when
 /// an action is intercepted (ie. mouse enter, exit, move, etc.)
 pub mod callbacks;
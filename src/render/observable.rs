@@ -0,0 +1,398 @@
+// Pushrod Rendering Library
+// Observable Values and Data Binding
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget::Widget;
+use crate::render::widget_cache::WidgetId;
+use crate::render::widget_config::{Config, ConfigKey, ConfigValue};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// This is the callback type that is called with the new value of an `Observable` when it changes.
+pub type ObserverCallbackType<T> = Box<dyn FnMut(&T)>;
+
+/// This identifies a callback that was added to an `Observable` with `subscribe`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubscriptionId(usize);
+
+/// This is the shared state of an `Observable`.
+struct ObservableState<T> {
+    value: T,
+    version: u64,
+    next_subscription: usize,
+    subscribers: Vec<(usize, ObserverCallbackType<T>)>,
+}
+
+/// This is a value in an application's model that notifies its subscribers when it changes, and
+/// that can be bound to a configuration value of a `Widget` with `Engine::bind`.  Cloning an
+/// `Observable` returns another handle to the same value, so it can be moved into callbacks.
+pub struct Observable<T> {
+    state: Rc<RefCell<ObservableState<T>>>,
+}
+
+/// This is the implementation of the `Observable`.
+impl<T: Clone + PartialEq + 'static> Observable<T> {
+    /// Creates a new `Observable` with an initial `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(ObservableState {
+                value,
+                version: 0,
+                next_subscription: 0,
+                subscribers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Returns a copy of the current value.
+    pub fn get(&self) -> T {
+        self.state.borrow().value.clone()
+    }
+
+    /// Changes the value, and calls every subscriber with the new value.  Nothing happens if the
+    /// value is unchanged.  Subscribers may read or change this `Observable`, but callbacks that
+    /// they add are not called until the next change.
+    pub fn set(&self, value: T) {
+        let mut subscribers = {
+            let mut state = self.state.borrow_mut();
+
+            if state.value == value {
+                return;
+            }
+
+            state.value = value.clone();
+            state.version += 1;
+            std::mem::take(&mut state.subscribers)
+        };
+
+        for (_, cb) in subscribers.iter_mut() {
+            cb(&value);
+        }
+
+        let mut state = self.state.borrow_mut();
+
+        subscribers.append(&mut state.subscribers);
+        state.subscribers = subscribers;
+    }
+
+    /// Adds a callback that is called with the new value whenever the value changes.  Returns a
+    /// `SubscriptionId`, which can be passed to `unsubscribe` to remove it.
+    pub fn subscribe<F>(&self, callback: F) -> SubscriptionId
+    where
+        F: FnMut(&T) + 'static,
+    {
+        let mut state = self.state.borrow_mut();
+        let id = state.next_subscription;
+
+        state.next_subscription += 1;
+        state.subscribers.push((id, Box::new(callback)));
+
+        SubscriptionId(id)
+    }
+
+    /// Removes a callback that was added with `subscribe`.
+    pub fn unsubscribe(&self, id: SubscriptionId) {
+        self.state
+            .borrow_mut()
+            .subscribers
+            .retain(|(x, _)| *x != id.0);
+    }
+
+    /// Returns a number that changes whenever the value changes.
+    fn version(&self) -> u64 {
+        self.state.borrow().version
+    }
+}
+
+impl<T> Clone for Observable<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// This controls the direction in which a binding between an `Observable` and a `Widget`
+/// configuration value copies changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingMode {
+    /// Changes to the `Observable` are copied to the `Widget`.
+    OneWay,
+
+    /// Changes to the `Observable` are copied to the `Widget`, and changes that the `Widget` makes
+    /// to the configuration value, ie. in response to user input, are copied to the `Observable`.
+    TwoWay,
+}
+
+/// This is a binding that is stored in the `WidgetCache`, which is synchronized in three steps,
+/// so that no `Widget` is borrowed while the subscribers of an `Observable` are called.
+pub(crate) trait Binding {
+    /// Returns the ID of the bound `Widget`.
+    fn widget_id(&self) -> WidgetId;
+
+    /// Picks up a change that the `Widget` made to the bound configuration value.
+    fn read_widget(&mut self, widget: &mut dyn Widget);
+
+    /// Copies a change that was picked up by `read_widget` to the `Observable`.
+    fn update_model(&mut self);
+
+    /// Copies a change of the `Observable` to the `Widget`, and invalidates it.
+    fn update_widget(&mut self, widget: &mut dyn Widget);
+}
+
+/// This binds an `Observable` to the configuration value of a `Widget` that is identified by a
+/// `ConfigKey`.
+pub(crate) struct ConfigBinding<T> {
+    widget_id: WidgetId,
+    key: ConfigKey<T>,
+    observable: Observable<T>,
+    mode: BindingMode,
+    synced_version: Option<u64>,
+    synced_value: Option<Config>,
+    widget_value: Option<T>,
+}
+
+/// This is the implementation of the `ConfigBinding`.
+impl<T: ConfigValue + Clone + PartialEq + 'static> ConfigBinding<T> {
+    /// Creates a new binding.  The value of the `observable` is copied to the `Widget` the first
+    /// time the binding is synchronized.
+    pub(crate) fn new(
+        widget_id: WidgetId,
+        key: ConfigKey<T>,
+        observable: Observable<T>,
+        mode: BindingMode,
+    ) -> Self {
+        Self {
+            widget_id,
+            key,
+            observable,
+            mode,
+            synced_version: None,
+            synced_value: None,
+            widget_value: None,
+        }
+    }
+}
+
+impl<T: ConfigValue + Clone + PartialEq + 'static> Binding for ConfigBinding<T> {
+    fn widget_id(&self) -> WidgetId {
+        self.widget_id
+    }
+
+    fn read_widget(&mut self, widget: &mut dyn Widget) {
+        if self.mode != BindingMode::TwoWay || self.synced_value.is_none() {
+            return;
+        }
+
+        let current = widget.get_config().get_config(self.key.id()).cloned();
+
+        if let Some(current) = current {
+            if Some(&current) != self.synced_value.as_ref() {
+                self.widget_value = T::from_config(&current);
+                self.synced_value = Some(current);
+            }
+        }
+    }
+
+    fn update_model(&mut self) {
+        if let Some(value) = self.widget_value.take() {
+            self.observable.set(value);
+            self.synced_version = Some(self.observable.version());
+        }
+    }
+
+    fn update_widget(&mut self, widget: &mut dyn Widget) {
+        let version = self.observable.version();
+
+        if self.synced_version == Some(version) {
+            return;
+        }
+
+        let value = self.observable.get().into_config();
+
+        self.synced_version = Some(version);
+        self.synced_value = Some(value.clone());
+
+        if widget.get_config().get_config(self.key.id()) != Some(&value) {
+            widget.set_config_value(self.key.id(), value);
+            widget.get_config().set_invalidate(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::callbacks::CallbackRegistry;
+    use crate::render::widget::TypedConfig;
+    use crate::render::widget_cache::{WidgetCache, WidgetContainer};
+    use crate::render::widget_config::{WidgetConfig, KEY_TEXT};
+    use crate::render::Points;
+    use std::cell::Cell;
+    use std::collections::HashMap;
+
+    /// This is a `Widget` that counts how many times its configuration is changed.
+    struct CountingWidget {
+        config: WidgetConfig,
+        system_properties: HashMap<i32, String>,
+        callback_registry: CallbackRegistry,
+        changes: Rc<Cell<u32>>,
+    }
+
+    impl CountingWidget {
+        fn new(changes: Rc<Cell<u32>>) -> Self {
+            Self {
+                config: WidgetConfig::new(0, 0, 10, 10),
+                system_properties: HashMap::new(),
+                callback_registry: CallbackRegistry::new(),
+                changes,
+            }
+        }
+    }
+
+    impl Widget for CountingWidget {
+        fn on_config_changed(&mut self, _k: u8, _v: Config) {
+            self.changes.set(self.changes.get() + 1);
+        }
+
+        default_widget_properties!();
+        default_widget_callbacks!();
+    }
+
+    fn text_of(cache: &WidgetCache, id: WidgetId) -> String {
+        let index = cache.get_widget_index(id).unwrap();
+
+        cache.get_widgets()[index as usize]
+            .widget
+            .borrow_mut()
+            .try_get(KEY_TEXT)
+            .unwrap()
+    }
+
+    fn set_text_of(cache: &WidgetCache, id: WidgetId, text: &str) {
+        let index = cache.get_widget_index(id).unwrap();
+
+        cache.get_widgets()[index as usize]
+            .widget
+            .borrow_mut()
+            .set_value(KEY_TEXT, String::from(text));
+    }
+
+    #[test]
+    fn observable_version_changes_only_with_the_value() {
+        let observable = Observable::new(1);
+        let calls = Rc::new(Cell::new(0));
+        let calls_clone = calls.clone();
+
+        observable.subscribe(move |_| calls_clone.set(calls_clone.get() + 1));
+        observable.set(1);
+
+        assert_eq!(observable.version(), 0);
+        assert_eq!(calls.get(), 0);
+
+        observable.set(2);
+        observable.set(2);
+
+        assert_eq!(observable.version(), 1);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(observable.clone().get(), 2);
+    }
+
+    #[test]
+    fn binding_copies_only_new_versions_to_the_widget() {
+        let changes = Rc::new(Cell::new(0));
+        let mut cache = WidgetCache::new();
+        let id = cache.add_widget(
+            Box::new(CountingWidget::new(changes.clone())),
+            String::from("widget"),
+        );
+        let observable = Observable::new(String::from("first"));
+
+        cache
+            .bind(id, KEY_TEXT, &observable, BindingMode::OneWay)
+            .unwrap();
+        cache.sync_bindings();
+
+        assert_eq!(text_of(&cache, id), "first");
+        assert_eq!(changes.get(), 1);
+
+        // Nothing changed, so the widget is left alone.
+        cache.sync_bindings();
+
+        assert_eq!(changes.get(), 1);
+
+        observable.set(String::from("second"));
+        cache.sync_bindings();
+
+        assert_eq!(text_of(&cache, id), "second");
+        assert_eq!(changes.get(), 2);
+
+        // A one-way binding does not copy changes back from the widget.
+        set_text_of(&cache, id, "typed");
+        cache.sync_bindings();
+
+        assert_eq!(observable.get(), "second");
+        assert_eq!(text_of(&cache, id), "typed");
+    }
+
+    #[test]
+    fn two_way_bindings_do_not_feed_changes_back() {
+        let first_changes = Rc::new(Cell::new(0));
+        let second_changes = Rc::new(Cell::new(0));
+        let mut cache = WidgetCache::new();
+        let first = cache.add_widget(
+            Box::new(CountingWidget::new(first_changes.clone())),
+            String::from("first"),
+        );
+        let second = cache.add_widget(
+            Box::new(CountingWidget::new(second_changes.clone())),
+            String::from("second"),
+        );
+        let observable = Observable::new(String::from("model"));
+        let notifications = Rc::new(Cell::new(0));
+        let notifications_clone = notifications.clone();
+
+        observable.subscribe(move |_| notifications_clone.set(notifications_clone.get() + 1));
+        cache
+            .bind(first, KEY_TEXT, &observable, BindingMode::TwoWay)
+            .unwrap();
+        cache
+            .bind(second, KEY_TEXT, &observable, BindingMode::TwoWay)
+            .unwrap();
+        cache.sync_bindings();
+
+        assert_eq!(first_changes.get(), 1);
+        assert_eq!(second_changes.get(), 1);
+
+        // A change in the first widget reaches the model and the second widget, but is not
+        // copied back to the first widget.
+        set_text_of(&cache, first, "typed");
+        cache.sync_bindings();
+
+        assert_eq!(observable.get(), "typed");
+        assert_eq!(notifications.get(), 1);
+        assert_eq!(text_of(&cache, second), "typed");
+        assert_eq!(first_changes.get(), 2);
+        assert_eq!(second_changes.get(), 2);
+
+        // The change that was copied to the second widget is not picked up as a new change.
+        for _ in 0..3 {
+            cache.sync_bindings();
+        }
+
+        assert_eq!(notifications.get(), 1);
+        assert_eq!(first_changes.get(), 2);
+        assert_eq!(second_changes.get(), 2);
+    }
+}
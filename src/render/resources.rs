@@ -0,0 +1,338 @@
+// Pushrod Rendering Library
+// Shared Font and Image Resources
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use log::debug;
+use sdl2::image::{ImageRWops, LoadSurface};
+use sdl2::rwops::RWops;
+use sdl2::surface::Surface;
+use sdl2::ttf::{Font, FontStyle, Sdl2TtfContext};
+use std::collections::HashMap;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+/// This is the name of the default font, which the built-in `Widget`s use for their text.  Use
+/// `ResourceCache::set_default_font` to replace it with another font.
+pub const DEFAULT_FONT: &str = "default";
+
+/// This is the path of the font that `DEFAULT_FONT` refers to, until it is replaced.
+pub const DEFAULT_FONT_PATH: &str = "assets/OpenSans-Regular.ttf";
+
+/// This is the path of the image that `CheckboxWidget` draws when it is selected.
+pub const CHECKBOX_SELECTED_IMAGE_PATH: &str = "assets/checkbox_selected.png";
+
+/// This is the path of the image that `CheckboxWidget` draws when it is not selected.
+pub const CHECKBOX_UNSELECTED_IMAGE_PATH: &str = "assets/checkbox_unselected.png";
+
+/// This is the TTF context that every `ResourceCache` loads its fonts with.  It is initialized
+/// when the first font is loaded, and is never dropped, as that would shut the TTF library down
+/// while fonts are still in use.
+static TTF_CONTEXT: OnceLock<Result<Sdl2TtfContext, String>> = OnceLock::new();
+
+/// These are the assets that the built-in `Widget`s use, which are compiled into the library when
+/// the `embedded-assets` feature is enabled, so that applications do not need to ship them.
+#[cfg(feature = "embedded-assets")]
+const EMBEDDED_ASSETS: [(&str, &[u8]); 3] = [
+    (
+        DEFAULT_FONT_PATH,
+        include_bytes!("../../assets/OpenSans-Regular.ttf"),
+    ),
+    (
+        CHECKBOX_SELECTED_IMAGE_PATH,
+        include_bytes!("../../assets/checkbox_selected.png"),
+    ),
+    (
+        CHECKBOX_UNSELECTED_IMAGE_PATH,
+        include_bytes!("../../assets/checkbox_unselected.png"),
+    ),
+];
+
+/// This is a `Font` that is stored in the `ResourceCache`.  Fonts are loaded from files, and use
+/// a TTF context that is shared by all `ResourceCache`s, and lives for the remainder of the
+/// application.
+pub type CachedFont = Rc<Font<'static, 'static>>;
+
+/// This is an image that is stored in the `ResourceCache`.
+pub type CachedImage = Rc<Surface<'static>>;
+
+/// This is a family of fonts that is registered under a name in the `ResourceCache`.  Each style
+/// variant is a separate font file.  When a style is requested that has no variant of its own,
+/// the `regular` font is rendered in that style instead.
+#[derive(Clone, Debug)]
+pub struct FontFamily {
+    /// The path of the regular font.
+    pub regular: String,
+
+    /// The path of the bold font, if any.
+    pub bold: Option<String>,
+
+    /// The path of the italic font, if any.
+    pub italic: Option<String>,
+
+    /// The path of the bold italic font, if any.
+    pub bold_italic: Option<String>,
+}
+
+/// This is the implementation of the `FontFamily`.
+impl FontFamily {
+    /// Creates a new `FontFamily` with only a `regular` font.
+    pub fn new(regular: String) -> Self {
+        Self {
+            regular,
+            bold: None,
+            italic: None,
+            bold_italic: None,
+        }
+    }
+
+    /// Returns the path of the font to use for `style`, along with the style that still needs to
+    /// be applied to it, as the bold and italic variants are already in their style.
+    fn resolve(&self, style: FontStyle) -> (&str, FontStyle) {
+        let bold_italic = FontStyle::BOLD | FontStyle::ITALIC;
+        let variants = [
+            (bold_italic, &self.bold_italic),
+            (FontStyle::BOLD, &self.bold),
+            (FontStyle::ITALIC, &self.italic),
+        ];
+
+        for (variant_style, path) in variants.iter() {
+            if let Some(path) = path {
+                if style.contains(*variant_style) {
+                    return (path, style - *variant_style);
+                }
+            }
+        }
+
+        (&self.regular, style)
+    }
+}
+
+/// This is a cache of the fonts and images that `Widget`s draw with, so that they are loaded from
+/// disk once, rather than every time a `Widget` is drawn.  Fonts are cached by their path, point
+/// size and style, and images by their path.  Images are stored as `Surface`s, as textures are
+/// bound to the canvas that created them; `RenderCanvas::copy_image` converts them as they are
+/// drawn, and keeps the texture if the canvas has a `TextureCache`.
+///
+/// The `WidgetCache` owns a `ResourceCache`, which `Widget`s can borrow during `draw` by calling
+/// `resources` on the `RenderCanvas` that they are given.  The `load_font` and `load_image`
+/// functions do this for you.
+///
+/// Fonts can be registered under a name as a `FontFamily` with `register_font`, and are then
+/// loaded by that name, rather than by path.  `DEFAULT_FONT` is registered by default.
+///
+/// The data of a font or image can also be registered in memory under a path with
+/// `register_asset`, in which case it is loaded from memory instead of from disk.  With the
+/// `embedded-assets` feature, which is enabled by default, the assets of the built-in `Widget`s
+/// are registered this way, so no `assets` directory is needed.  Registering an asset under the
+/// same path replaces the built-in asset.
+pub struct ResourceCache {
+    assets: HashMap<String, &'static [u8]>,
+    font_families: HashMap<String, FontFamily>,
+    fonts: HashMap<(String, u16, i32), CachedFont>,
+    images: HashMap<String, CachedImage>,
+}
+
+/// This is the default `ResourceCache`, which has only `DEFAULT_FONT` registered.
+impl Default for ResourceCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// This is the implementation of the `ResourceCache`.
+impl ResourceCache {
+    /// Creates a new, empty `ResourceCache`.
+    pub fn new() -> Self {
+        let mut font_families = HashMap::new();
+
+        font_families.insert(
+            String::from(DEFAULT_FONT),
+            FontFamily::new(String::from(DEFAULT_FONT_PATH)),
+        );
+
+        #[allow(unused_mut)]
+        let mut assets = HashMap::new();
+
+        #[cfg(feature = "embedded-assets")]
+        for (path, data) in EMBEDDED_ASSETS.iter() {
+            assets.insert(String::from(*path), *data);
+        }
+
+        Self {
+            assets,
+            font_families,
+            fonts: HashMap::new(),
+            images: HashMap::new(),
+        }
+    }
+
+    /// Registers the contents of a font or image file, `data`, under `path`, so that loading
+    /// `path` reads it from memory rather than from disk.  Use `include_bytes!` to embed the file
+    /// in an application.  Any asset previously registered under `path` is replaced, and fonts and
+    /// images that were already loaded are released.
+    pub fn register_asset(&mut self, path: String, data: &'static [u8]) {
+        self.fonts.clear();
+        self.images.clear();
+        self.assets.insert(path, data);
+    }
+
+    /// Registers `family` under `name`, replacing any family previously registered under that
+    /// name.  Fonts that were already loaded under that name are released.
+    pub fn register_font(&mut self, name: String, family: FontFamily) {
+        self.fonts.clear();
+        self.font_families.insert(name, family);
+    }
+
+    /// Replaces the font that is registered as `DEFAULT_FONT`.  `Widget`s must be redrawn to use
+    /// the new font, ie. by calling `EngineHandle::request_repaint`.
+    pub fn set_default_font(&mut self, family: FontFamily) {
+        self.register_font(String::from(DEFAULT_FONT), family);
+    }
+
+    /// Returns the `FontFamily` registered under `name`, if any.
+    pub fn get_font_family(&self, name: &str) -> Option<&FontFamily> {
+        self.font_families.get(name)
+    }
+
+    /// Returns the font `name`, in the point `size` and `style` given, loading it if it has not
+    /// been loaded before.  `name` is either the name of a registered `FontFamily`, or the path of
+    /// a font file.  Returns an error if the font could not be loaded.
+    pub fn get_font(
+        &mut self,
+        name: &str,
+        size: u16,
+        style: FontStyle,
+    ) -> Result<CachedFont, PushrodError> {
+        let (path, style) = match self.font_families.get(name) {
+            Some(family) => {
+                let (path, style) = family.resolve(style);
+
+                (String::from(path), style)
+            }
+            None => (String::from(name), style),
+        };
+        let key = (path.clone(), size, style.bits());
+
+        if let Some(font) = self.fonts.get(&key) {
+            return Ok(font.clone());
+        }
+
+        let font_error = |e: String| PushrodError::FontLoad(path.clone(), e);
+        let ttf_context = TTF_CONTEXT
+            .get_or_init(|| sdl2::ttf::init().map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(|e| font_error(e.clone()))?;
+
+        let mut font = match self.assets.get(&path) {
+            Some(data) => ttf_context
+                .load_font_from_rwops(RWops::from_bytes(data).map_err(font_error)?, size)
+                .map_err(font_error)?,
+            None => ttf_context
+                .load_font(Path::new(&path), size)
+                .map_err(font_error)?,
+        };
+
+        font.set_style(style);
+
+        debug!(
+            target: "pushrod::resources",
+            "Font loaded: path={} size={} style={:?}",
+            path,
+            size,
+            style
+        );
+
+        let font = Rc::new(font);
+
+        self.fonts.insert(key, font.clone());
+
+        Ok(font)
+    }
+
+    /// Returns the image at `path`, loading it if it has not been loaded before.  Returns an error
+    /// if the image could not be loaded.
+    pub fn get_image(&mut self, path: &str) -> Result<CachedImage, PushrodError> {
+        if let Some(image) = self.images.get(path) {
+            return Ok(image.clone());
+        }
+
+        let image_error = |e: String| PushrodError::ImageLoad(String::from(path), e);
+        let image = match self.assets.get(path) {
+            Some(data) => {
+                let rwops = RWops::from_bytes(data).map_err(image_error)?;
+                let surface = rwops.load().map_err(image_error)?;
+
+                // The loaded surface is tied to the lifetime of the `RWops`, so it is copied into
+                // a surface that owns its pixels.
+                surface
+                    .convert(&surface.pixel_format())
+                    .map_err(image_error)?
+            }
+            None => Surface::from_file(Path::new(path)).map_err(image_error)?,
+        };
+        let image = Rc::new(image);
+
+        debug!(target: "pushrod::resources", "Image loaded: path={}", path);
+
+        self.images.insert(String::from(path), image.clone());
+
+        Ok(image)
+    }
+
+    /// Removes all fonts and images from the cache, so that they are loaded again the next time
+    /// they are used.  Resources that are still in use by a `Widget` remain valid.
+    pub fn clear(&mut self) {
+        self.fonts.clear();
+        self.images.clear();
+    }
+}
+
+/// This is the reason that is reported when a resource is loaded through a canvas that has no
+/// `ResourceCache`.
+const NO_RESOURCES: &str = "the canvas has no ResourceCache";
+
+/// Returns the font `name`, in the point `size` and `style` given, from the `ResourceCache` of
+/// the canvas `c`.  `name` is either the name of a registered `FontFamily`, or the path of a font
+/// file.  Returns an error if the canvas has no `ResourceCache`, which is only the case when a
+/// `Widget` is drawn outside of a `WidgetCache`.
+pub fn load_font(
+    c: &mut dyn RenderCanvas,
+    name: &str,
+    size: u16,
+    style: FontStyle,
+) -> Result<CachedFont, PushrodError> {
+    match c.resources() {
+        Some(resources) => resources.get_font(name, size, style),
+        None => Err(PushrodError::FontLoad(
+            String::from(name),
+            String::from(NO_RESOURCES),
+        )),
+    }
+}
+
+/// Returns the image at `path` from the `ResourceCache` of the canvas `c`.  Returns an error if
+/// the canvas has no `ResourceCache`, which is only the case when a `Widget` is drawn outside of a
+/// `WidgetCache`.
+pub fn load_image(c: &mut dyn RenderCanvas, path: &str) -> Result<CachedImage, PushrodError> {
+    match c.resources() {
+        Some(resources) => resources.get_image(path),
+        None => Err(PushrodError::ImageLoad(
+            String::from(path),
+            String::from(NO_RESOURCES),
+        )),
+    }
+}
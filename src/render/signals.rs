@@ -0,0 +1,375 @@
+// Pushrod Rendering Library
+// Named Signals
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::widget::Widget;
+use crate::render::widget_cache::{WidgetContainer, WidgetId};
+use log::{trace, warn};
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// This is a named signal whose payload is of type `T`.  Signals are emitted through an
+/// `EngineHandle`, and delivered by the `Engine` to every slot that is connected to the signal
+/// with `Engine::connect` or `Engine::connect_widget`.  Signals are declared as constants:
+///
+/// ```ignore
+/// pub const VOLUME_CHANGED: Signal<i32> = Signal::new("my_crate::volume_changed");
+/// ```
+///
+/// Signals are identified by their name, so names should be prefixed with the name of the crate
+/// that declares them.
+pub struct Signal<T> {
+    name: &'static str,
+    _payload: PhantomData<fn() -> T>,
+}
+
+/// This is the implementation of the `Signal`.
+impl<T> Signal<T> {
+    /// Declares a signal called `name`.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _payload: PhantomData,
+        }
+    }
+
+    /// Returns the name of this signal.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Signal<T> {}
+
+impl<T> fmt::Debug for Signal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Signal({})", self.name)
+    }
+}
+
+/// This identifies a slot that was connected to a signal, which can be passed to
+/// `Engine::disconnect` to remove it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SlotId(usize);
+
+/// This is the callback type of a slot, which receives the payload of the signal, and the list of
+/// `WidgetContainer`s in the cache.
+type SlotCallbackType = Box<dyn FnMut(&dyn Any, &[WidgetContainer])>;
+
+/// This is a slot that is connected to a signal.
+struct Slot {
+    id: SlotId,
+    widget_id: Option<WidgetId>,
+    callback: SlotCallbackType,
+}
+
+/// This is a signal that has been emitted, and is waiting to be delivered.
+pub(crate) struct PendingSignal {
+    name: &'static str,
+    payload: Box<dyn Any>,
+}
+
+/// This is the implementation of the `PendingSignal`.
+impl PendingSignal {
+    /// Creates a new `PendingSignal` for `signal`, carrying `payload`.
+    pub(crate) fn new<T: 'static>(signal: Signal<T>, payload: T) -> Self {
+        Self {
+            name: signal.name,
+            payload: Box::new(payload),
+        }
+    }
+}
+
+/// This is the registry of slots that the `Engine` delivers signals to.
+#[derive(Default)]
+pub(crate) struct SignalSlots {
+    slots: HashMap<&'static str, Vec<Slot>>,
+    next_slot: usize,
+}
+
+/// This is the implementation of the `SignalSlots`.
+impl SignalSlots {
+    /// Creates a new, empty registry.
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: HashMap::new(),
+            next_slot: 0,
+        }
+    }
+
+    /// Connects a `callback` to `signal`.
+    pub(crate) fn connect<T, F>(&mut self, signal: Signal<T>, mut callback: F) -> SlotId
+    where
+        T: 'static,
+        F: FnMut(&T, &[WidgetContainer]) + 'static,
+    {
+        self.add_slot(
+            signal,
+            None,
+            Box::new(move |payload, widgets| match payload.downcast_ref::<T>() {
+                Some(payload) => callback(payload, widgets),
+                None => payload_mismatch(signal),
+            }),
+        )
+    }
+
+    /// Connects a `callback` to `signal`, which is called with the `Widget` specified by
+    /// `widget_id`.  The `Widget` is invalidated after the callback returns.
+    pub(crate) fn connect_widget<T, F>(
+        &mut self,
+        widget_id: WidgetId,
+        signal: Signal<T>,
+        mut callback: F,
+    ) -> SlotId
+    where
+        T: 'static,
+        F: FnMut(&mut dyn Widget, &T, &[WidgetContainer]) + 'static,
+    {
+        self.add_slot(
+            signal,
+            Some(widget_id),
+            Box::new(move |payload, widgets| {
+                let payload = match payload.downcast_ref::<T>() {
+                    Some(payload) => payload,
+                    None => return payload_mismatch(signal),
+                };
+
+                if let Some(container) = widgets.iter().find(|x| x.get_id() == widget_id) {
+                    match container.widget.try_borrow_mut() {
+                        Ok(mut widget) => {
+                            callback(&mut **widget, payload, widgets);
+                            widget.get_config().set_invalidate(true);
+                        }
+
+                        Err(_) => warn!(
+                            target: "pushrod::events",
+                            "Widget is in use, signal not delivered: id={:?}",
+                            widget_id
+                        ),
+                    }
+                }
+            }),
+        )
+    }
+
+    /// Removes the slot specified by `id`.
+    pub(crate) fn disconnect(&mut self, id: SlotId) {
+        for slots in self.slots.values_mut() {
+            slots.retain(|x| x.id != id);
+        }
+    }
+
+    /// Delivers `signal` to every slot that is connected to it, in the order in which they were
+    /// connected.  Slots of `Widget`s that no longer exist are removed.
+    pub(crate) fn deliver(&mut self, signal: PendingSignal, widgets: &[WidgetContainer]) {
+        let slots = match self.slots.get_mut(signal.name) {
+            Some(slots) => slots,
+            None => {
+                trace!(
+                    target: "pushrod::events",
+                    "Signal has no slots: {}",
+                    signal.name
+                );
+                return;
+            }
+        };
+
+        slots.retain(|slot| match slot.widget_id {
+            Some(widget_id) => widgets.iter().any(|x| x.get_id() == widget_id),
+            None => true,
+        });
+
+        trace!(
+            target: "pushrod::events",
+            "Signal delivered: name={} slots={}",
+            signal.name,
+            slots.len()
+        );
+
+        for slot in slots.iter_mut() {
+            (slot.callback)(&*signal.payload, widgets);
+        }
+    }
+
+    fn add_slot<T>(
+        &mut self,
+        signal: Signal<T>,
+        widget_id: Option<WidgetId>,
+        callback: SlotCallbackType,
+    ) -> SlotId {
+        let id = SlotId(self.next_slot);
+
+        self.next_slot += 1;
+        self.slots.entry(signal.name).or_default().push(Slot {
+            id,
+            widget_id,
+            callback,
+        });
+
+        id
+    }
+}
+
+/// Reports a signal that was emitted with a payload of another type than that of the slot, which
+/// happens when two `Signal`s are declared with the same name.
+fn payload_mismatch<T>(signal: Signal<T>) {
+    warn!(
+        target: "pushrod::events",
+        "Signal payload has an unexpected type, not delivered: {}",
+        signal.name
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::widget::BaseWidget;
+    use crate::render::widget_cache::WidgetCache;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    const NUMBER: Signal<i32> = Signal::new("pushrod::tests::number");
+    const OTHER: Signal<i32> = Signal::new("pushrod::tests::other");
+    const NUMBER_AS_TEXT: Signal<String> = Signal::new("pushrod::tests::number");
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    fn new_cache() -> (WidgetCache, WidgetId) {
+        let mut cache = WidgetCache::new();
+
+        cache.add_widget(
+            Box::new(BaseWidget::new(0, 0, 400, 300)),
+            String::from("base"),
+        );
+
+        let widget_id = cache.add_widget(
+            Box::new(BaseWidget::new(10, 10, 50, 50)),
+            String::from("widget"),
+        );
+
+        (cache, widget_id)
+    }
+
+    fn connect_logger(slots: &mut SignalSlots, log: &Log, name: &'static str) -> SlotId {
+        let log = log.clone();
+
+        slots.connect(NUMBER, move |payload, _| {
+            log.borrow_mut().push(format!("{}={}", name, payload))
+        })
+    }
+
+    #[test]
+    fn deliver_calls_slots_in_connection_order() {
+        let (cache, widget_id) = new_cache();
+        let mut slots = SignalSlots::new();
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let widget_log = log.clone();
+
+        connect_logger(&mut slots, &log, "first");
+        slots.connect_widget(widget_id, NUMBER, move |_, payload, _| {
+            widget_log.borrow_mut().push(format!("widget={}", payload))
+        });
+        connect_logger(&mut slots, &log, "last");
+
+        let index = cache.get_widget_index(widget_id).unwrap() as usize;
+        let widget = &cache.get_widgets()[index].widget;
+
+        widget.borrow_mut().get_config().set_invalidate(false);
+        slots.deliver(PendingSignal::new(NUMBER, 7), cache.get_widgets());
+        slots.deliver(PendingSignal::new(OTHER, 8), cache.get_widgets());
+
+        assert_eq!(*log.borrow(), vec!["first=7", "widget=7", "last=7"]);
+
+        // The widget is invalidated by the slot, so that it is redrawn.
+        assert!(widget.borrow_mut().get_config().invalidated());
+    }
+
+    #[test]
+    fn disconnected_slots_are_not_called() {
+        let (cache, _) = new_cache();
+        let mut slots = SignalSlots::new();
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+
+        connect_logger(&mut slots, &log, "first");
+        let second = connect_logger(&mut slots, &log, "second");
+        connect_logger(&mut slots, &log, "third");
+
+        slots.disconnect(second);
+        slots.disconnect(second);
+        slots.deliver(PendingSignal::new(NUMBER, 1), cache.get_widgets());
+
+        assert_eq!(*log.borrow(), vec!["first=1", "third=1"]);
+    }
+
+    #[test]
+    fn slots_of_removed_widgets_are_pruned() {
+        let (mut cache, widget_id) = new_cache();
+        let mut slots = SignalSlots::new();
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let widget_log = log.clone();
+
+        slots.connect_widget(widget_id, NUMBER, move |_, payload, _| {
+            widget_log.borrow_mut().push(format!("widget={}", payload))
+        });
+        connect_logger(&mut slots, &log, "global");
+
+        cache.remove_widget(widget_id).unwrap();
+        slots.deliver(PendingSignal::new(NUMBER, 2), cache.get_widgets());
+
+        assert_eq!(*log.borrow(), vec!["global=2"]);
+        assert_eq!(slots.slots[NUMBER.name()].len(), 1);
+
+        // A new widget that reuses the slot of the removed one does not receive the signal.
+        cache.add_widget(
+            Box::new(BaseWidget::new(10, 10, 50, 50)),
+            String::from("new widget"),
+        );
+        slots.deliver(PendingSignal::new(NUMBER, 3), cache.get_widgets());
+
+        assert_eq!(*log.borrow(), vec!["global=2", "global=3"]);
+    }
+
+    #[test]
+    fn slots_ignore_payloads_of_another_type() {
+        let (cache, widget_id) = new_cache();
+        let mut slots = SignalSlots::new();
+        let log: Log = Rc::new(RefCell::new(Vec::new()));
+        let widget_log = log.clone();
+        let text_log = log.clone();
+
+        connect_logger(&mut slots, &log, "number");
+        slots.connect_widget(widget_id, NUMBER, move |_, payload, _| {
+            widget_log.borrow_mut().push(format!("widget={}", payload))
+        });
+        slots.connect(NUMBER_AS_TEXT, move |payload, _| {
+            text_log.borrow_mut().push(format!("text={}", payload))
+        });
+
+        slots.deliver(
+            PendingSignal::new(NUMBER_AS_TEXT, String::from("four")),
+            cache.get_widgets(),
+        );
+        slots.deliver(PendingSignal::new(NUMBER, 5), cache.get_widgets());
+
+        assert_eq!(*log.borrow(), vec!["text=four", "number=5", "widget=5"]);
+    }
+}
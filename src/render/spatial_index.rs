@@ -0,0 +1,155 @@
+// Pushrod Rendering Library
+// Spatial Index for Hit Testing
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use sdl2::rect::Rect;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// This is the width and height of each cell of the `SpatialIndex` grid, in pixels.
+pub const SPATIAL_CELL_SIZE: i32 = 64;
+
+/// This is an entry in the `SpatialIndex`, which stores the area of a `Widget` in `Window`
+/// coordinates, clipped to the areas of its ancestors, along with its position in drawing order.
+#[derive(Clone, Debug)]
+struct SpatialEntry {
+    widget_id: i32,
+    area: Rect,
+    rank: usize,
+}
+
+/// This is a uniform grid that maps each cell of the `Window` to the `Widget`s whose area
+/// overlaps it, so that the `Widget` under a point can be found without testing every `Widget`.
+/// Each `Widget` is stored with its drawing order rank, so the top-most `Widget` at a point is the
+/// one with the highest rank.
+#[derive(Default)]
+pub struct SpatialIndex {
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    entries: Vec<SpatialEntry>,
+}
+
+/// This is the implementation of the `SpatialIndex`.
+impl SpatialIndex {
+    /// Creates a new, empty `SpatialIndex`.
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Removes all entries from the index.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.entries.clear();
+    }
+
+    /// Adds the `Widget` specified by `widget_id` to the index.  The `area` is in `Window`
+    /// coordinates, and `rank` is the position of the `Widget` in drawing order: `Widget`s with a
+    /// higher rank are drawn above those with a lower rank.
+    pub fn insert(&mut self, widget_id: i32, area: Rect, rank: usize) {
+        let entry_index = self.entries.len();
+        let (left, top) = Self::cell_of(area.x(), area.y());
+        let (right, bottom) = Self::cell_of(area.right() - 1, area.bottom() - 1);
+
+        self.entries.push(SpatialEntry {
+            widget_id,
+            area,
+            rank,
+        });
+
+        for cell_y in top..=bottom {
+            for cell_x in left..=right {
+                self.cells
+                    .entry((cell_x, cell_y))
+                    .or_default()
+                    .push(entry_index);
+            }
+        }
+    }
+
+    /// Returns the IDs of all `Widget`s whose area contains the point at `x` and `y`, with the
+    /// top-most `Widget` first.
+    pub fn query(&self, x: i32, y: i32) -> Vec<i32> {
+        let mut found: Vec<&SpatialEntry> = match self.cells.get(&Self::cell_of(x, y)) {
+            Some(entries) => entries
+                .iter()
+                .map(|index| &self.entries[*index])
+                .filter(|entry| entry.area.contains_point((x, y)))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        found.sort_by_key(|x| Reverse(x.rank));
+        found.iter().map(|x| x.widget_id).collect()
+    }
+
+    fn cell_of(x: i32, y: i32) -> (i32, i32) {
+        (
+            x.div_euclid(SPATIAL_CELL_SIZE),
+            y.div_euclid(SPATIAL_CELL_SIZE),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_returns_overlapping_widgets_top_most_first() {
+        let mut index = SpatialIndex::new();
+
+        index.insert(1, Rect::new(0, 0, 100, 100), 1);
+        index.insert(3, Rect::new(50, 50, 100, 100), 3);
+        index.insert(2, Rect::new(25, 25, 100, 100), 2);
+
+        assert_eq!(index.query(75, 75), vec![3, 2, 1]);
+        assert_eq!(index.query(30, 30), vec![2, 1]);
+        assert_eq!(index.query(10, 10), vec![1]);
+        assert!(index.query(200, 200).is_empty());
+    }
+
+    #[test]
+    fn query_finds_widgets_spanning_several_cells() {
+        let mut index = SpatialIndex::new();
+        let size = SPATIAL_CELL_SIZE as u32;
+
+        index.insert(1, Rect::new(-10, -10, size * 3, size * 2), 1);
+
+        assert_eq!(index.query(-10, -10), vec![1]);
+        assert_eq!(
+            index.query(SPATIAL_CELL_SIZE * 2, SPATIAL_CELL_SIZE),
+            vec![1]
+        );
+
+        // The right and bottom edges of an area are exclusive.
+        assert!(index.query(SPATIAL_CELL_SIZE * 3 - 10, 0).is_empty());
+        assert!(index.query(0, SPATIAL_CELL_SIZE * 2 - 10).is_empty());
+    }
+
+    #[test]
+    fn clear_removes_all_widgets() {
+        let mut index = SpatialIndex::new();
+
+        index.insert(1, Rect::new(0, 0, 10, 10), 1);
+        index.clear();
+
+        assert!(index.query(5, 5).is_empty());
+
+        index.insert(2, Rect::new(0, 0, 10, 10), 1);
+
+        assert_eq!(index.query(5, 5), vec![2]);
+    }
+}
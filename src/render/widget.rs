@@ -14,14 +14,20 @@
 // limitations under the License.
 
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
-use sdl2::video::Window;
 
 use crate::render::callbacks::*;
+use crate::render::canvas::RenderCanvas;
+use crate::render::drag_drop::DragPayload;
+use crate::render::error::PushrodError;
+use crate::render::messages::apply_config;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
 use crate::render::{Points, Size};
+use log::warn;
+use sdl2::keyboard::{Keycode, Mod};
 use sdl2::pixels::Color;
+use sdl2::rect::Point;
+use std::any::Any;
 use std::collections::HashMap;
 
 pub trait ConfigConvenience {}
@@ -36,11 +42,22 @@ pub trait ConfigConvenience {}
 /// these automatically generated implementation details could change in future releases of this
 /// library, so it is best to use the default implementation if possible.
 pub trait Widget {
-    /// Draws the widget.  If you wish to modify the canvas object, you must declare it as `mut` in
-    /// your implementation (ie `fn draw(&mut self, mut canvas: Canvas<Window>)`).  The `_canvas`
-    /// is the currently active drawing canvas at the time this function is called.  This called
-    /// during the draw loop of the `Engine`.
-    fn draw(&mut self, _c: &mut Canvas<Window>) {}
+    /// Draws the widget.  The `_c` is the currently active `RenderCanvas` at the time this function
+    /// is called, which may draw to a `Window`, or to an offscreen `Surface`.  This called during
+    /// the draw loop of the `Engine`.
+    fn draw(&mut self, _c: &mut dyn RenderCanvas) {}
+
+    /// Draws the widget, returning an error if it could not be drawn, ie. if a font or image
+    /// could not be loaded.  The `Engine` draws `Widget`s by calling this function, and when an
+    /// error is returned, draws a placeholder in place of the `Widget`, and reports the error to
+    /// its `on_error` callback.  By default, this calls `draw`.
+    ///
+    /// `Widget`s that can fail to draw should implement this function instead, and implement
+    /// `draw` by calling `draw_with_placeholder`.
+    fn try_draw(&mut self, _c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
+        self.draw(_c);
+        Ok(())
+    }
 
     /// Retrieves the `WidgetConfig` object for this `Widget`.
     fn get_config(&mut self) -> &mut WidgetConfig;
@@ -51,6 +68,13 @@ pub trait Widget {
     /// Retrieves a `Callback` registry for this `Widget`.
     fn get_callbacks(&mut self) -> &mut CallbackRegistry;
 
+    /// Retrieves this `Widget` as an `Any` reference, which can be downcast to the concrete type
+    /// of the `Widget` using `downcast_mut`.  This is implemented by the
+    /// `default_widget_properties!` macro.  `Widget`s that do not use the macro implement it by
+    /// returning `self`.  It has no default, as a default would have to require `Self: Sized`,
+    /// which would make it unavailable on `dyn Widget`.
+    fn as_any(&mut self) -> &mut dyn Any;
+
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.  This function
     /// implementation is **optional**.
     fn mouse_entered(&mut self, _widgets: &[WidgetContainer]) {
@@ -97,12 +121,124 @@ pub trait Widget {
         self.button_clicked_callback(_widgets, _button, _clicks, _state);
     }
 
+    /// When a key is pressed while this `Widget` has keyboard focus, this function is called.  It
+    /// contains the `Keycode` of the key that was pressed, and the `Mod` modifier keys that were
+    /// held down at the time.  This function implementation is **optional**.
+    fn key_pressed(&mut self, _widgets: &[WidgetContainer], _keycode: Keycode, _keymod: Mod) {
+        self.key_pressed_callback(_widgets, _keycode, _keymod);
+    }
+
+    /// When a key is released while this `Widget` has keyboard focus, this function is called.  It
+    /// contains the `Keycode` of the key that was released, and the `Mod` modifier keys that were
+    /// held down at the time.  This function implementation is **optional**.
+    fn key_released(&mut self, _widgets: &[WidgetContainer], _keycode: Keycode, _keymod: Mod) {
+        self.key_released_callback(_widgets, _keycode, _keymod);
+    }
+
+    /// When text is entered while this `Widget` has keyboard focus, this function is called.  The
+    /// `text` contains the committed text, which may be more than one character when entered
+    /// through an input method (IME).  This function implementation is **optional**.
+    fn text_input(&mut self, _widgets: &[WidgetContainer], _text: String) {}
+
+    /// When an input method (IME) composition is in progress while this `Widget` has keyboard
+    /// focus, this function is called.  The `text` contains the pre-edit text that has not yet
+    /// been committed, `start` is the cursor position within the pre-edit text, and `length` is
+    /// the length of the selection within it.  This function implementation is **optional**.
+    fn text_edited(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _text: String,
+        _start: i32,
+        _length: i32,
+    ) {
+    }
+
+    /// When this `Widget` receives keyboard focus, this function is called.  Focus is assigned
+    /// when the `Widget` is clicked, or when the focus is set programmatically through the
+    /// `WidgetCache`.  This function implementation is **optional**.
+    fn focus_gained(&mut self, _widgets: &[WidgetContainer]) {
+        self.focus_gained_callback(_widgets);
+    }
+
+    /// When this `Widget` loses keyboard focus to another `Widget`, this function is called.  This
+    /// function implementation is **optional**.
+    fn focus_lost(&mut self, _widgets: &[WidgetContainer]) {
+        self.focus_lost_callback(_widgets);
+    }
+
+    /// When the mouse first moves while a button is held down over this `Widget`, this function is
+    /// called.  It contains the `X` and `Y` coordinates of where the button was pressed.  From this
+    /// point on, this `Widget` captures the mouse, receiving all `mouse_moved` and `drag` calls
+    /// until the button is released, even if the mouse leaves its bounds.  This function
+    /// implementation is **optional**.
+    fn drag_start(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+        self.drag_start_callback(_widgets, _points);
+    }
+
+    /// When the mouse moves during a drag that started on this `Widget`, this function is called.
+    /// It contains the `X` and `Y` coordinates of the mouse, which may be outside of the bounds of
+    /// the `Widget`.  This function implementation is **optional**.
+    fn drag(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+        self.drag_callback(_widgets, _points);
+    }
+
+    /// When the mouse button that started a drag on this `Widget` is released, this function is
+    /// called.  It contains the `X` and `Y` coordinates of where the button was released.  This
+    /// function implementation is **optional**.
+    fn drag_end(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+        self.drag_end_callback(_widgets, _points);
+    }
+
+    /// When a drag starts on this `Widget`, this function is called to determine whether or not
+    /// the drag is a drag and drop operation.  Return a `DragPayload` to start dragging it, or
+    /// `None` to treat the drag as a plain mouse drag.  This function implementation is
+    /// **optional**, and returns `None` by default.
+    fn drag_payload(&mut self) -> Option<DragPayload> {
+        None
+    }
+
+    /// When a drag and drop operation moves over this `Widget`, this function is called to
+    /// determine whether or not the `Widget` is a drop target for the `payload`.  By default, this
+    /// returns `true` if an `on_drop` callback has been set.
+    fn accepts_drop(&mut self, _payload: &DragPayload) -> bool {
+        self.get_callbacks().has_on_drop()
+    }
+
+    /// When an accepted drag and drop operation moves over this `Widget`, this function is called,
+    /// so the `Widget` can indicate that it is a drop target.  This function implementation is
+    /// **optional**.
+    fn drop_entered(&mut self, _widgets: &[WidgetContainer]) {
+        self.drop_entered_callback(_widgets);
+    }
+
+    /// When an accepted drag and drop operation leaves this `Widget`, is dropped on it, or is
+    /// cancelled, this function is called, so the `Widget` can remove its drop target indication.
+    /// This function implementation is **optional**.
+    fn drop_exited(&mut self, _widgets: &[WidgetContainer]) {
+        self.drop_exited_callback(_widgets);
+    }
+
+    /// When a `DragPayload` that this `Widget` accepts is dropped on it, this function is called.
+    /// It contains the payload, and the `X` and `Y` coordinates of the drop.  This function
+    /// implementation is **optional**.
+    fn dropped(&mut self, _widgets: &[WidgetContainer], _payload: DragPayload, _points: Points) {
+        self.dropped_callback(_widgets, _payload, _points);
+    }
+
     /// When a timer tick goes by (ie. a frame is displayed on the screen), this function is
     /// called.  This function implementation is **optional**.
     fn tick(&mut self, _widgets: &[WidgetContainer]) {
         self.tick_callback(_widgets);
     }
 
+    /// Indicates whether or not this `Widget` needs to receive `tick` calls, such as when a timer
+    /// or animation is running.  When no `Widget` needs a `tick` and nothing is invalidated, the
+    /// `Engine` may sleep until the next event arrives.  By default, this returns `true` if an
+    /// `on_tick` callback has been set.
+    fn needs_tick(&mut self) -> bool {
+        self.get_callbacks().has_on_tick()
+    }
+
     /// This calls the `on_tick` callback.  This is implemented by the `default_widget_callbacks!` macro,
     /// so you do not need to implement it.  However, you need to call this function if you wish
     /// to honor an `on_tick` callback.
@@ -140,12 +276,98 @@ pub trait Widget {
     ) {
     }
 
+    /// This calls the `on_key_pressed` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_key_pressed` callback.
+    fn key_pressed_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _keycode: Keycode,
+        _keymod: Mod,
+    ) {
+    }
+
+    /// This calls the `on_key_released` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_key_released` callback.
+    fn key_released_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _keycode: Keycode,
+        _keymod: Mod,
+    ) {
+    }
+
+    /// This calls the `on_focus_gained` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_focus_gained` callback.
+    fn focus_gained_callback(&mut self, _widgets: &[WidgetContainer]) {}
+
+    /// This calls the `on_focus_lost` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_focus_lost` callback.
+    fn focus_lost_callback(&mut self, _widgets: &[WidgetContainer]) {}
+
+    /// This calls the `on_drag_start` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_drag_start` callback.
+    fn drag_start_callback(&mut self, _widgets: &[WidgetContainer], _points: Points) {}
+
+    /// This calls the `on_drag` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_drag` callback.
+    fn drag_callback(&mut self, _widgets: &[WidgetContainer], _points: Points) {}
+
+    /// This calls the `on_drag_end` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_drag_end` callback.
+    fn drag_end_callback(&mut self, _widgets: &[WidgetContainer], _points: Points) {}
+
+    /// This calls the `on_drop_entered` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_drop_entered` callback.
+    fn drop_entered_callback(&mut self, _widgets: &[WidgetContainer]) {}
+
+    /// This calls the `on_drop_exited` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_drop_exited` callback.
+    fn drop_exited_callback(&mut self, _widgets: &[WidgetContainer]) {}
+
+    /// This calls the `on_drop` callback.  This is implemented by the `default_widget_callbacks!` macro,
+    /// so you do not need to implement it.  However, you need to call this function if you wish
+    /// to honor an `on_drop` callback.
+    fn dropped_callback(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _payload: DragPayload,
+        _points: Points,
+    ) {
+    }
+
     /// This callback is called when a setter is used to configure a value.  It is _not_ called when a
     /// call to `get_config()` using the setter is called, so it is best to use the top-level setters
     /// and getters for the configuration values - at least, until the `get_config()` call can be made
     /// private.
     fn on_config_changed(&mut self, _k: u8, _v: Config) {}
 
+    /// This callback is called when a configuration value declared with `ConfigKey::new` is set
+    /// through `set_config_value` or `set_value`.  Values of built-in keys are reported to
+    /// `on_config_changed` instead.
+    fn on_named_config_changed(&mut self, _k: ConfigKeyId, _v: Config) {}
+
+    /// Sets the configuration value identified by `key`.  Values of built-in keys are set through
+    /// the setter for their type, such as `set_color`, so `on_config_changed` is called.  Values of
+    /// keys declared with `ConfigKey::new` are reported to `on_named_config_changed`.
+    fn set_config_value(&mut self, key: ConfigKeyId, value: Config) {
+        match key {
+            ConfigKeyId::Builtin(k) => apply_config(self, k, value),
+            ConfigKeyId::Named(_, _) => {
+                self.get_config().set_config(key, value.clone());
+                self.on_named_config_changed(key, value);
+            }
+        }
+    }
+
     /// Sets a point for a configuration key.
     fn set_point(&mut self, config: u8, x: i32, y: i32) {
         self.get_config().set_point(config, x, y);
@@ -182,46 +404,179 @@ pub trait Widget {
         self.on_config_changed(config, Config::CompassPosition(value.clone()));
     }
 
+    /// Sets a fractional value for a configuration key.
+    fn set_float(&mut self, config: u8, value: f64) {
+        self.get_config().set_float(config, value);
+        self.on_config_changed(config, Config::Float(value));
+    }
+
+    /// Sets a `Rect` for a configuration key.
+    fn set_rect(&mut self, config: u8, rect: Rect) {
+        self.get_config().set_rect(config, rect);
+        self.on_config_changed(config, Config::Rect(rect));
+    }
+
+    /// Sets `Insets` for a configuration key.
+    fn set_insets(&mut self, config: u8, insets: Insets) {
+        self.get_config().set_insets(config, insets);
+        self.on_config_changed(config, Config::Insets(insets));
+    }
+
+    /// Sets a list of `String`s for a configuration key.
+    fn set_string_list(&mut self, config: u8, list: Vec<String>) {
+        self.get_config().set_string_list(config, list.clone());
+        self.on_config_changed(config, Config::StringList(list));
+    }
+
+    /// Sets a font reference for a configuration key.
+    fn set_font(&mut self, config: u8, font: FontRef) {
+        self.get_config().set_font(config, font.clone());
+        self.on_config_changed(config, Config::Font(font));
+    }
+
+    /// Sets an image reference for a configuration key.
+    fn set_image(&mut self, config: u8, image: ImageRef) {
+        self.get_config().set_image(config, image.clone());
+        self.on_config_changed(config, Config::Image(image));
+    }
+
     /// Retrieves a `Points` for a configuration key.  Returns `Points::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_point(&mut self, k: u8) -> Points {
         self.get_config().get_point(k)
     }
 
     /// Retrieves a `Size` for a configuration key.  Returns a `Size::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_size(&mut self, k: u8) -> Size {
         self.get_config().get_size(k)
     }
 
     /// Retrieves a `Color` for a configuration key.  Returns white if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_color(&mut self, k: u8) -> Color {
         self.get_config().get_color(k)
     }
 
     /// Retrieves a numeric value for a configuration key.  Returns 0 if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_numeric(&mut self, k: u8) -> i32 {
         self.get_config().get_numeric(k)
     }
 
     /// Retrieves text for a configuration key.  Returns a blank string if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_text(&mut self, k: u8) -> String {
         self.get_config().get_text(k)
     }
 
     /// Retrieves a boolean toggle for a configuration key.  Returns `false` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_toggle(&mut self, k: u8) -> bool {
         self.get_config().get_toggle(k)
     }
 
-    /// Retrieves a `CompassPosition` toggle for a configuration key.  Returns `CompassPosition::W` if not set.
+    /// Retrieves a `CompassPosition` toggle for a configuration key.  Returns `CompassPosition::W`
+    /// if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
     fn get_compass(&mut self, k: u8) -> CompassPosition {
         self.get_config().get_compass(k)
     }
 
+    /// Retrieves a fractional value for a configuration key.  Returns 0.0 if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
+    fn get_float(&mut self, k: u8) -> f64 {
+        self.get_config().get_float(k)
+    }
+
+    /// Retrieves a `Rect` for a configuration key.  Returns a `1x1` `Rect` at `0x0` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
+    fn get_rect(&mut self, k: u8) -> Rect {
+        self.get_config().get_rect(k)
+    }
+
+    /// Retrieves `Insets` for a configuration key.  Returns `Insets::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
+    fn get_insets(&mut self, k: u8) -> Insets {
+        self.get_config().get_insets(k)
+    }
+
+    /// Retrieves a list of `String`s for a configuration key.  Returns an empty list if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
+    fn get_string_list(&mut self, k: u8) -> Vec<String> {
+        self.get_config().get_string_list(k)
+    }
+
+    /// Retrieves a font reference for a configuration key.  Returns `FontRef::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
+    fn get_font(&mut self, k: u8) -> FontRef {
+        self.get_config().get_font(k)
+    }
+
+    /// Retrieves an image reference for a configuration key.  Returns `ImageRef::default` if not
+    /// set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    #[allow(deprecated)]
+    fn get_image(&mut self, k: u8) -> ImageRef {
+        self.get_config().get_image(k)
+    }
+
     /// Sets the origin of the `Widget`, adjusting the X and Y coordinates.  Automatically sets the
     /// `invalidate` flag to `true` when adjusted, but only if the new origin is not the same as
     /// the previous origin.
     fn set_origin(&mut self, _origin: Points) {
-        let old_origin = self.get_config().get_point(CONFIG_ORIGIN);
+        let old_origin = self.get_config().try_get(KEY_ORIGIN).unwrap_or_default();
 
         if _origin[0] != old_origin[0] || _origin[1] != old_origin[1] {
             self.get_config()
@@ -234,7 +589,7 @@ pub trait Widget {
     /// sets the `invalidate` flag to `true` when adjusted, but only if the new size is not the
     /// same as the previous size.
     fn set_size(&mut self, _size: Vec<u32>) {
-        let old_size = self.get_config().get_size(CONFIG_SIZE);
+        let old_size = self.get_config().try_get(KEY_SIZE).unwrap_or_default();
 
         if _size[0] != old_size[0] || _size[1] != old_size[1] {
             self.get_config().set_size(CONFIG_SIZE, _size[0], _size[1]);
@@ -247,12 +602,65 @@ pub trait Widget {
         Rect::new(
             self.get_config().to_x(0),
             self.get_config().to_y(0),
-            self.get_config().get_size(CONFIG_SIZE)[0],
-            self.get_config().get_size(CONFIG_SIZE)[1],
+            self.get_config().try_get(KEY_SIZE).unwrap_or_default()[0],
+            self.get_config().try_get(KEY_SIZE).unwrap_or_default()[1],
         )
     }
 }
 
+/// This trait provides the typed configuration functions of a `Widget`, which take a `ConfigKey`.
+/// Generic functions cannot be called through a trait object, so they are not part of the `Widget`
+/// trait, but are implemented here for every `Widget`, including `dyn Widget`.
+pub trait TypedConfig {
+    /// Sets the `value` of a typed configuration `key`, in the same way as `set_config_value`.
+    fn set_value<T: ConfigValue>(&mut self, key: ConfigKey<T>, value: T);
+
+    /// Retrieves the value of a typed configuration `key`.  Returns an error if the value is not
+    /// set, or is of another type.
+    fn try_get<T: ConfigValue>(&mut self, key: ConfigKey<T>) -> Result<T, ConfigError>;
+}
+
+/// This is the implementation of `TypedConfig` for all `Widget`s.
+impl<W: Widget + ?Sized> TypedConfig for W {
+    fn set_value<T: ConfigValue>(&mut self, key: ConfigKey<T>, value: T) {
+        self.set_config_value(key.id(), value.into_config());
+    }
+
+    fn try_get<T: ConfigValue>(&mut self, key: ConfigKey<T>) -> Result<T, ConfigError> {
+        self.get_config().try_get(key)
+    }
+}
+
+/// Draws a placeholder in the `area` given, which is drawn in place of a `Widget` that could not
+/// be drawn: a light gray box with a red border, crossed from corner to corner.
+pub fn draw_placeholder(c: &mut dyn RenderCanvas, area: Rect) {
+    // Errors are ignored here, as there is nothing left to fall back to.
+    c.set_draw_color(Color::RGB(224, 224, 224));
+    let _ = c.fill_rect(area);
+
+    c.set_draw_color(Color::RGB(255, 0, 0));
+    let _ = c.draw_rect(area);
+    let _ = c.draw_line(
+        Point::new(area.left(), area.top()),
+        Point::new(area.right() - 1, area.bottom() - 1),
+    );
+    let _ = c.draw_line(
+        Point::new(area.right() - 1, area.top()),
+        Point::new(area.left(), area.bottom() - 1),
+    );
+}
+
+/// Draws the `widget` by calling its `try_draw` function, and draws a placeholder in its place if
+/// an error is returned.  The error is logged, as there is no `Engine` to report it to.  This is
+/// used to implement `draw` for `Widget`s that implement `try_draw`.
+pub fn draw_with_placeholder(widget: &mut dyn Widget, c: &mut dyn RenderCanvas) {
+    if let Err(e) = widget.try_draw(c) {
+        warn!(target: "pushrod::draw", "Widget could not be drawn: {}", e);
+
+        draw_placeholder(c, widget.get_drawing_area());
+    }
+}
+
 /// This is an example top-level `Widget` object that is used to draw a background and a border
 /// of specified colors.  `COLOR_BASE` determines the background fill color, and the `COLOR_BORDER`
 /// determines the color of the border.  The width of the border is controlled by the
@@ -277,28 +685,55 @@ impl BaseWidget {
 
 /// Implementation for drawing a `BaseWidget`, with the `Widget` trait objects applied.
 impl Widget for BaseWidget {
-    fn draw(&mut self, mut _canvas: &mut Canvas<Window>) {
-        let base_color = self.get_config().get_color(CONFIG_COLOR_BASE);
-        let border_color = self.get_config().get_color(CONFIG_COLOR_BORDER);
+    fn draw(&mut self, _canvas: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, _canvas);
+    }
 
-        _canvas.set_draw_color(base_color);
+    /// Draws the background and border, returning an error if the canvas could not be drawn to.
+    fn try_draw(&mut self, _canvas: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
+        let base_color = self
+            .get_config()
+            .try_get(KEY_COLOR_BASE)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let border_color = self
+            .get_config()
+            .try_get(KEY_COLOR_BORDER)
+            .unwrap_or(Color::RGB(255, 255, 255));
 
-        _canvas.fill_rect(self.get_drawing_area()).unwrap();
+        _canvas.set_draw_color(base_color);
 
-        if self.get_config().get_numeric(CONFIG_BORDER_WIDTH) > 0 && base_color != border_color {
+        _canvas
+            .fill_rect(self.get_drawing_area())
+            .map_err(PushrodError::Render)?;
+
+        if self
+            .get_config()
+            .try_get(KEY_BORDER_WIDTH)
+            .unwrap_or_default()
+            > 0
+            && base_color != border_color
+        {
             _canvas.set_draw_color(border_color);
 
-            for border in 0..self.get_config().get_numeric(CONFIG_BORDER_WIDTH) {
+            for border in 0..self
+                .get_config()
+                .try_get(KEY_BORDER_WIDTH)
+                .unwrap_or_default()
+            {
                 _canvas
                     .draw_rect(Rect::new(
                         self.config.to_x(border),
                         self.config.to_y(border),
-                        self.get_config().get_size(CONFIG_SIZE)[0] - (border as u32 * 2),
-                        self.get_config().get_size(CONFIG_SIZE)[1] - (border as u32 * 2),
+                        self.get_config().try_get(KEY_SIZE).unwrap_or_default()[0]
+                            - (border as u32 * 2),
+                        self.get_config().try_get(KEY_SIZE).unwrap_or_default()[1]
+                            - (border as u32 * 2),
                     ))
-                    .unwrap();
+                    .map_err(PushrodError::Render)?;
             }
         }
+
+        Ok(())
     }
 
     default_widget_properties!();
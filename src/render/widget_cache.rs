@@ -13,24 +13,72 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
-use crate::render::widget::Widget;
-use crate::render::widget_config::{CONFIG_ORIGIN, CONFIG_SIZE};
-use sdl2::pixels::Color;
+use log::{debug, trace, warn};
+
+use crate::render::canvas::{OffsetCanvas, RenderCanvas};
+use crate::render::drag_drop::DragPayload;
+use crate::render::error::PushrodError;
+use crate::render::messages::{apply_config, WidgetMessage, WidgetTarget};
+use crate::render::observable::{Binding, BindingMode, ConfigBinding, Observable};
+use crate::render::resources::ResourceCache;
+use crate::render::spatial_index::SpatialIndex;
+use crate::render::widget::{draw_placeholder, Widget};
+use crate::render::widget_config::{ConfigKey, ConfigValue, KEY_ORIGIN};
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::pixels::{Color, PixelFormatEnum};
 use sdl2::rect::Rect;
-use sdl2::render::Canvas;
-use sdl2::video::Window;
+use sdl2::surface::Surface;
+
+/// This is a handle to a `Widget` stored in the `WidgetCache`, which is returned when a `Widget`
+/// is added.  Unlike the numeric `Widget` ID, which is the position of the `Widget` in the cache
+/// and changes when other `Widget`s are removed, a `WidgetId` always refers to the same `Widget`.
+/// Once that `Widget` is removed, the `WidgetId` becomes stale, and lookups using it return `None`,
+/// even if its storage is reused by a new `Widget`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WidgetId {
+    slot: u32,
+    generation: u32,
+}
+
+/// This is an entry in the slot table of the `WidgetCache`, which maps a `WidgetId` to the
+/// position of its `Widget` in the cache.
+#[derive(Default)]
+struct WidgetSlot {
+    generation: u32,
+    index: Option<usize>,
+}
+
+/// This is the layer that a `Widget` is drawn in.  `Widget`s in the `Overlay` layer are drawn
+/// above all `Widget`s in the `Normal` layer, and are found first when hit testing, regardless of
+/// the order in which they were added.  Overlays are intended for popups, dropdown lists and
+/// tooltips.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WidgetLayer {
+    /// The layer that `Widget`s are added to by default.
+    Normal,
+
+    /// The layer that is drawn above all `Normal` `Widget`s.
+    Overlay,
+}
 
 /// This is a container that stores information about a `Widget` that will be drawn on the screen.
-/// It stores the `Widget` object, the actual point of origin inside the `Window` (as a `Vec<i32>`
-/// of X and Y points), the parent ID of this `Widget`, if it is being added as a child.
+/// It stores the `Widget` object, the point of origin relative to its parent at the time it was
+/// added (as a `Vec<i32>` of X and Y points), and the parent ID of this `Widget`, if it is being
+/// added as a child.
 pub struct WidgetContainer {
     pub widget: RefCell<Box<dyn Widget>>,
     widget_name: String,
     pub origin: Vec<i32>,
     widget_id: i32,
     parent_id: i32,
+    id: WidgetId,
+    layer: WidgetLayer,
+    z_order: i32,
+    painted_area: Option<Rect>,
+    render_cache: Option<Rc<Surface<'static>>>,
 }
 
 /// This is the `WidgetContainer` object that stores a `Widget` and its accompanying information:
@@ -43,6 +91,7 @@ impl WidgetContainer {
         origin: Vec<i32>,
         widget_id: i32,
         parent_id: i32,
+        id: WidgetId,
     ) -> Self {
         Self {
             widget: RefCell::new(widget),
@@ -50,6 +99,11 @@ impl WidgetContainer {
             origin: origin.clone(),
             widget_id,
             parent_id,
+            id,
+            layer: WidgetLayer::Normal,
+            z_order: 0,
+            painted_area: None,
+            render_cache: None,
         }
     }
 
@@ -58,116 +112,359 @@ impl WidgetContainer {
         self.widget_name.clone()
     }
 
-    /// Retrieves the numeric ID of this `Widget`.
+    /// Retrieves the numeric ID of this `Widget`, which is its position in the `WidgetContainer`
+    /// list.  This changes when `Widget`s before it are removed, so use `get_id` to keep a
+    /// reference to a `Widget`.
     pub fn get_widget_id(&self) -> i32 {
         self.widget_id
     }
 
+    /// Retrieves the `WidgetId` handle of this `Widget`.
+    pub fn get_id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Calls `f` with this `Widget`, downcast to its concrete type `T`, returning the result.  This
+    /// allows methods that are specific to a `Widget` type to be called, ie.
+    /// `container.with_widget(|t: &mut TimerWidget| t.disable())`.  Returns `None` if the `Widget`
+    /// is not of type `T`, or if it is already borrowed, which is the case for the `Widget` whose
+    /// callback is currently running.
+    pub fn with_widget<T, R, F>(&self, f: F) -> Option<R>
+    where
+        T: Widget + 'static,
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut widget = self.widget.try_borrow_mut().ok()?;
+
+        widget.as_any().downcast_mut::<T>().map(f)
+    }
+
     /// Retrieves the numeric ID of the parent that this `Widget` refers to.  A `0` indicates
     /// no parent is assigned.
     pub fn get_parent_id(&self) -> i32 {
         self.parent_id
     }
+
+    /// Retrieves the `WidgetLayer` that this `Widget` is drawn in.
+    pub fn get_layer(&self) -> WidgetLayer {
+        self.layer
+    }
+
+    /// Retrieves the stacking order of this `Widget` among its siblings in the same layer.
+    /// `Widget`s with a higher value are drawn above those with a lower value.
+    pub fn get_z_order(&self) -> i32 {
+        self.z_order
+    }
 }
 
 /// This is the `WidgetCache` struct, which contains a list of `Widget`s that are managed by the Pushrod
 /// `Engine`.  `Widget` IDs are automatically generated by the `WidgetCache`, which automatically
 /// assigns the `Widget` ID at the time it's added to the cache.  Parent IDs must already exist,
-/// otherwise, an error is thrown at the time the `Widget` is attempted to be added.  `Widget` IDs
-/// always start at 1.
+/// otherwise, an error is thrown at the time the `Widget` is attempted to be added.  The top-level
+/// `Widget` always has the ID 0.
+///
+/// Numeric `Widget` IDs are positions in the cache, which change as `Widget`s are removed.  Adding
+/// a `Widget` returns a `WidgetId` handle, which remains valid for as long as the `Widget` exists,
+/// and can be resolved to the current numeric ID using `get_widget_index`.
+///
+/// The `WidgetCache` also tracks keyboard focus.  Only one `Widget` can hold focus at a time, and
+/// all key events are routed to it.  A focus ID of `0` indicates that the top-level `Widget` has
+/// focus, meaning no `Widget` has been explicitly focused.
+///
+/// `Widget`s are drawn in the order in which they were added, with children drawn above their
+/// parent.  The stacking order of a `Widget` among its siblings can be changed with `raise`,
+/// `lower`, `bring_to_front` and `send_to_back`, and `Widget`s added with `add_overlay` are drawn
+/// above everything else.
+///
+/// `find_widget` uses a `SpatialIndex` of the `Widget` areas, which is rebuilt the next time it is
+/// needed after a `Widget` is added, removed or restacked, or changes its origin or size.
+///
+/// Fonts and images that `Widget`s load while drawing are kept in a `ResourceCache`, which can be
+/// retrieved with `get_resources`.
+///
+/// `Widget`s are drawn by calling `try_draw`.  A `Widget` that returns an error is drawn as a
+/// placeholder, and the error is kept until it is retrieved with `take_errors`.
+///
+/// Drawing only repaints damaged areas of the screen.  The area of each `Widget` that is
+/// invalidated, or that moves, resizes, is hidden or shown, is damaged both where it was last
+/// drawn and where it is now.  Every `Widget` that intersects a damaged area is then redrawn in
+/// drawing order, clipped to the damage, so that overlapping `Widget`s stay correct.
 #[derive(Default)]
 pub struct WidgetCache {
     cache: Vec<WidgetContainer>,
+    slots: Vec<WidgetSlot>,
+    free_slots: Vec<u32>,
+    focused_widget_id: i32,
+    next_z_order: i32,
+    spatial_index: SpatialIndex,
+    geometry_changed: Rc<Cell<bool>>,
+    pending_damage: Vec<Rect>,
+    damage: Vec<Rect>,
+    resources: ResourceCache,
+    errors: Vec<(WidgetId, PushrodError)>,
+    bindings: Vec<Box<dyn Binding>>,
 }
 
 /// This is the `WidgetCache` implementation.  This cache object manages the `Widget` list for use by the
 /// Pushrod `Engine`.
 impl WidgetCache {
     pub fn new() -> Self {
-        Self { cache: Vec::new() }
+        Self {
+            cache: Vec::new(),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            focused_widget_id: 0,
+            next_z_order: 0,
+            spatial_index: SpatialIndex::new(),
+            geometry_changed: Rc::new(Cell::new(true)),
+            pending_damage: Vec::new(),
+            damage: Vec::new(),
+            resources: ResourceCache::new(),
+            errors: Vec::new(),
+            bindings: Vec::new(),
+        }
     }
 
     /// This adds a `Widget` to the render list.  It requires that the `Widget` being added is in a `Box`,
-    /// along with a `widget_name`.  Returns the ID of the `Widget` that was added.  Use this ID if
-    /// you plan on adding further `Widget`s, with this `Widget` as the parent.  The point of
+    /// along with a `widget_name`.  Returns the `WidgetId` of the `Widget` that was added.  Use this
+    /// ID if you plan on adding further `Widget`s, with this `Widget` as the parent.  The point of
     /// `origin` (extracted from the `Widget`'s position at creation time) is its physical location
     /// inside the `Window`.
-    pub fn add_widget(&mut self, mut widget: Box<dyn Widget>, widget_name: String) -> i32 {
-        let origin = widget.get_config().get_point(CONFIG_ORIGIN);
-        let widget_id = self.cache.len();
+    pub fn add_widget(&mut self, widget: Box<dyn Widget>, widget_name: String) -> WidgetId {
+        self.push_widget(widget, widget_name, 0, WidgetLayer::Normal)
+    }
 
-        self.cache.push(WidgetContainer::new(
-            widget,
-            widget_name.clone(),
-            origin,
-            widget_id as i32,
-            0,
-        ));
+    /// This adds a `Widget` to the overlay layer, which is drawn above all other top-level
+    /// `Widget`s, and their children, regardless of when they were added.  Overlay `Widget`s are
+    /// also found first by `find_widget`.  Children can be added to an overlay `Widget` with
+    /// `add_child`.  Returns the `WidgetId` of the `Widget` that was added.
+    pub fn add_overlay(&mut self, widget: Box<dyn Widget>, widget_name: String) -> WidgetId {
+        self.push_widget(widget, widget_name, 0, WidgetLayer::Overlay)
+    }
 
-        (self.cache.len() - 1) as i32
+    /// This adds a `Widget` to the render list as a child of the `Widget` specified by `parent_id`.
+    /// The point of `origin` of the child is relative to the origin of its parent, and the child is
+    /// clipped to the drawing area of its parent.  A child is hidden or disabled when any of its
+    /// ancestors are.  Returns the `WidgetId` of the `Widget` that was added, or an error if the
+    /// parent does not exist, or has been removed.
+    pub fn add_child(
+        &mut self,
+        parent_id: WidgetId,
+        widget: Box<dyn Widget>,
+        widget_name: String,
+    ) -> Result<WidgetId, String> {
+        match self.get_widget_index(parent_id) {
+            Some(parent_index) => {
+                Ok(self.push_widget(widget, widget_name, parent_index, WidgetLayer::Normal))
+            }
+            None => Err(format!("Parent widget does not exist: id={:?}", parent_id)),
+        }
     }
 
-    /// This locates the ID of a `Widget` at a given `x` and `y` coordinate.  If a `Widget` could not
-    /// be found, the top-level `Widget` (id 0) is returned.  This function returns the top-most
-    /// visible `Widget` id.
-    pub fn find_widget(&mut self, x: i32, y: i32) -> i32 {
-        let mut found_widget_id: i32 = 0;
+    /// Removes the `Widget` specified by `widget_id` from the cache, along with all of its
+    /// children.  If the focused `Widget` is removed, focus is cleared first.  The area that the
+    /// removed `Widget`s covered is damaged, so that it is redrawn.  The numeric IDs of
+    /// the remaining `Widget`s may change, but their `WidgetId`s remain valid.  Returns an error if
+    /// the `Widget` does not exist, or if it is the top-level `Widget`.
+    ///
+    /// While the `Engine` is running, use `Engine::remove_widget` instead, which also updates the
+    /// mouse state that the `Engine` keeps.
+    pub fn remove_widget(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        let index = match self.get_widget_index(widget_id) {
+            Some(0) => return Err(String::from("The top-level widget cannot be removed")),
+            Some(index) => index as usize,
+            None => return Err(format!("Widget does not exist: id={:?}", widget_id)),
+        };
 
-        for i in 0..self.cache.len() {
-            if !self.is_hidden(i as i32) {
-                let start_x: i32 = self.cache[i]
-                    .widget
-                    .borrow_mut()
-                    .get_config()
-                    .get_point(CONFIG_ORIGIN)[0];
-                let start_y: i32 = self.cache[i]
-                    .widget
-                    .borrow_mut()
-                    .get_config()
-                    .get_point(CONFIG_ORIGIN)[1];
-                let end_x: i32 = start_x
-                    + (self.cache[i]
-                        .widget
-                        .borrow_mut()
-                        .get_config()
-                        .get_size(CONFIG_SIZE)[0] as i32);
-                let end_y: i32 = start_y
-                    + (self.cache[i]
-                        .widget
-                        .borrow_mut()
-                        .get_config()
-                        .get_size(CONFIG_SIZE)[1] as i32);
-
-                if x >= start_x && x <= end_x && y >= start_y && y <= end_y {
-                    found_widget_id = i as i32;
-                }
+        // Children are always stored after their parent, so a single pass finds the subtree.
+        let mut removed = vec![false; self.cache.len()];
+
+        removed[index] = true;
+
+        for i in (index + 1)..self.cache.len() {
+            if removed[self.cache[i].parent_id as usize] {
+                removed[i] = true;
+            }
+        }
+
+        if removed[self.focused_widget_id as usize] {
+            self.set_focus(0);
+        }
+
+        let containers = std::mem::take(&mut self.cache);
+        let mut new_index = vec![0; containers.len()];
+
+        for (i, mut container) in containers.into_iter().enumerate() {
+            let slot = container.id.slot;
+
+            if removed[i] {
+                debug!(
+                    target: "pushrod::cache",
+                    "Widget removed: id={} name={}",
+                    i,
+                    container.widget_name
+                );
+
+                self.pending_damage.extend(container.painted_area);
+                self.slots[slot as usize].index = None;
+                self.slots[slot as usize].generation += 1;
+                self.free_slots.push(slot);
+            } else {
+                new_index[i] = self.cache.len() as i32;
+                container.widget_id = new_index[i];
+                container.parent_id = new_index[container.parent_id as usize];
+                self.slots[slot as usize].index = Some(self.cache.len());
+                self.cache.push(container);
             }
         }
 
-        found_widget_id
+        self.focused_widget_id = new_index[self.focused_widget_id as usize];
+        self.geometry_changed.set(true);
+
+        Ok(())
+    }
+
+    /// Moves the `Widget` specified by `widget_id` one step up in the stacking order of its
+    /// siblings, so that it is drawn above the sibling that was directly above it.  `Widget`s never
+    /// move between layers.  Returns an error if the `Widget` does not exist, or if it is the
+    /// top-level `Widget`.
+    pub fn raise(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        let index = self.get_restack_index(widget_id)?;
+        let siblings = self.get_layer_siblings_of(index);
+        let position = siblings.iter().position(|x| *x == index).unwrap();
+
+        if position + 1 < siblings.len() {
+            self.swap_z_order(index, siblings[position + 1]);
+        }
+
+        Ok(())
     }
 
-    /// Returns a `WidgetContainer` object by its ID.  This is the same `Widget` ID that is returned
-    /// when using the `add_widget` function.  There are no bounds checks here, so if the ID does not
-    /// exist, it will throw an exception at runtime.  Be careful: it's better to use the
-    /// `get_container_by_name` function to avoid this.
-    pub fn get_container_by_id(&mut self, id: i32) -> &mut WidgetContainer {
-        &mut self.cache[id as usize]
+    /// Moves the `Widget` specified by `widget_id` one step down in the stacking order of its
+    /// siblings, so that it is drawn below the sibling that was directly below it.  Returns an
+    /// error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn lower(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        let index = self.get_restack_index(widget_id)?;
+        let siblings = self.get_layer_siblings_of(index);
+        let position = siblings.iter().position(|x| *x == index).unwrap();
+
+        if position > 0 {
+            self.swap_z_order(index, siblings[position - 1]);
+        }
+
+        Ok(())
     }
 
-    /// Returns a `WidgetContainer` object by the name of the `Widget`.  If the `WidgetContainer`
-    /// cannot find the `Widget` by the `name` specified, the top-level `Widget` is returned for
-    /// safety.
-    pub fn get_container_by_name(&mut self, name: String) -> &mut WidgetContainer {
-        let cache_size = self.cache.len();
+    /// Moves the `Widget` specified by `widget_id` above all of its siblings in the same layer.
+    /// Returns an error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn bring_to_front(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        let index = self.get_restack_index(widget_id)?;
 
-        for i in 0..cache_size {
-            if self.cache[i].get_widget_name() == name {
-                return self.get_container_by_id(i as i32);
+        self.next_z_order += 1;
+        self.set_z_order(index, self.next_z_order);
+
+        Ok(())
+    }
+
+    /// Moves the `Widget` specified by `widget_id` below all of its siblings in the same layer.
+    /// Returns an error if the `Widget` does not exist, or if it is the top-level `Widget`.
+    pub fn send_to_back(&mut self, widget_id: WidgetId) -> Result<(), String> {
+        let index = self.get_restack_index(widget_id)?;
+        let lowest = self
+            .get_layer_siblings_of(index)
+            .iter()
+            .map(|x| self.cache[*x as usize].z_order)
+            .min()
+            .unwrap();
+
+        self.set_z_order(index, lowest - 1);
+
+        Ok(())
+    }
+
+    /// Returns the current numeric ID of the `Widget` referred to by `widget_id`, or `None` if the
+    /// `Widget` has been removed.
+    pub fn get_widget_index(&self, widget_id: WidgetId) -> Option<i32> {
+        match self.slots.get(widget_id.slot as usize) {
+            Some(slot) if slot.generation == widget_id.generation => {
+                slot.index.map(|index| index as i32)
             }
+            _ => None,
+        }
+    }
+
+    /// Returns the `WidgetId` of the `Widget` with the numeric ID `widget_id`, or `None` if there is
+    /// no such `Widget`.
+    pub fn get_id_of(&self, widget_id: i32) -> Option<WidgetId> {
+        if widget_id < 0 {
+            return None;
+        }
+
+        self.cache.get(widget_id as usize).map(|x| x.id)
+    }
+
+    /// Returns the list of `WidgetContainer`s that are stored in the cache, in the same form that
+    /// is passed to `Widget` callbacks.
+    pub fn get_widgets(&self) -> &[WidgetContainer] {
+        &self.cache
+    }
+
+    /// This locates the ID of a `Widget` at a given `x` and `y` coordinate.  If a `Widget` could not
+    /// be found, the top-level `Widget` (id 0) is returned.  This function returns the top-most
+    /// visible `Widget` id, in drawing order.  Children are only found inside the drawing area of
+    /// their parent, and hidden `Widget`s and their children are never found.
+    pub fn find_widget(&mut self, x: i32, y: i32) -> i32 {
+        if self.geometry_changed.replace(false) {
+            self.rebuild_spatial_index();
+        }
+
+        self.spatial_index
+            .query(x, y)
+            .into_iter()
+            .find(|widget_id| !self.is_hidden(*widget_id))
+            .unwrap_or(0)
+    }
+
+    /// Returns the drawing area of the `Widget` specified by `widget_id` in `Window` coordinates,
+    /// taking the origins of its parents into account.
+    pub fn get_absolute_drawing_area(&mut self, widget_id: i32) -> Rect {
+        let (offset_x, offset_y) = self.get_offset_of(widget_id);
+        let area = self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .get_drawing_area();
+
+        Rect::new(
+            area.x() + offset_x,
+            area.y() + offset_y,
+            area.width(),
+            area.height(),
+        )
+    }
+
+    /// Returns a `WidgetContainer` object by its `WidgetId`, as returned by the `add_widget`
+    /// function.  Returns `None` if the `Widget` has been removed.
+    pub fn get_container(&mut self, id: WidgetId) -> Option<&mut WidgetContainer> {
+        match self.get_widget_index(id) {
+            Some(index) => self.cache.get_mut(index as usize),
+            None => None,
+        }
+    }
+
+    /// Returns a `WidgetContainer` object by its numeric ID.  Returns `None` if there is no
+    /// `Widget` with that ID.
+    pub fn get_container_by_id(&mut self, id: i32) -> Option<&mut WidgetContainer> {
+        if id < 0 {
+            return None;
         }
 
-        self.get_container_by_id(0 as i32)
+        self.cache.get_mut(id as usize)
+    }
+
+    /// Returns a `WidgetContainer` object by the name of the `Widget`.  Returns `None` if no
+    /// `Widget` has the `name` specified.
+    pub fn get_container_by_name(&mut self, name: String) -> Option<&mut WidgetContainer> {
+        self.cache.iter_mut().find(|x| x.widget_name == name)
     }
 
     /// This function calls the `button_clicked` callback for the `Widget` specified by `widget_id`.
@@ -196,8 +493,13 @@ impl WidgetCache {
     }
 
     /// This function calls the `mouse_moved` callback for the `Widget` specified by `widget_id`.
+    /// The `points` are given in `Window` coordinates, and are translated into the coordinate
+    /// space of the `Widget` (the same space as its origin) before they are delivered.  The same
+    /// applies to the `drag_start`, `drag`, `drag_end` and `dropped` functions.
     pub fn mouse_moved(&mut self, widget_id: i32, points: Vec<i32>) {
         if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            let points = self.to_local(widget_id, points);
+
             self.cache[widget_id as usize]
                 .widget
                 .borrow_mut()
@@ -235,121 +537,1231 @@ impl WidgetCache {
         }
     }
 
-    /// This function calls the `tick` method on all registered `Widget`s in the cache.  The purpose
-    /// for the `tick` is to indicate that a drawing loop is about to occur, and the `Widget` can
-    /// update itself as necessary beforehand.
-    pub fn tick(&mut self) {
-        let cache_size = self.cache.len();
+    /// This function calls the `drag_start` callback for the `Widget` specified by `widget_id`.
+    pub fn drag_start(&mut self, widget_id: i32, points: Vec<i32>) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            let points = self.to_local(widget_id, points);
 
-        for i in 0..cache_size {
-            if !self.is_hidden(i as i32) {
-                self.cache[i].widget.borrow_mut().tick(&self.cache);
-            }
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .drag_start(&self.cache, points);
         }
     }
 
-    /// This function performs the draw loop for all of the `Widget`s stored in the `cache`.  Each
-    /// `Widget` receives a mutable reference to the `Canvas` so that the `Widget` can be drawn on
-    /// the screen during the draw loop of the `Engine`.  This `draw_loop` function automatically
-    /// clips the screen area so that the `Widget` cannot draw outside of its bounds.
-    pub fn draw_loop(&mut self, canvas: &mut Canvas<Window>) {
-        let cache_size = self.cache.len();
+    /// This function calls the `drag` callback for the `Widget` specified by `widget_id`.
+    pub fn drag(&mut self, widget_id: i32, points: Vec<i32>) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            let points = self.to_local(widget_id, points);
 
-        for i in 0..cache_size {
-            if self.cache[i].widget.borrow_mut().get_config().invalidated() {
-                self.draw(0, canvas);
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .drag(&self.cache, points);
+        }
+    }
 
-                return;
-            }
+    /// This function calls the `drag_end` callback for the `Widget` specified by `widget_id`.
+    pub fn drag_end(&mut self, widget_id: i32, points: Vec<i32>) {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            let points = self.to_local(widget_id, points);
+
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .drag_end(&self.cache, points);
         }
     }
 
-    // Private functions
+    /// Returns the `DragPayload` that the `Widget` specified by `widget_id` offers when a drag
+    /// starts on it, if any.
+    pub fn drag_payload(&mut self, widget_id: i32) -> Option<DragPayload> {
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .drag_payload()
+        } else {
+            None
+        }
+    }
 
-    fn get_children_of(&mut self, widget_id: i32) -> Vec<i32> {
-        self.cache
-            .iter()
-            .filter(|x| x.parent_id == widget_id)
-            .map(|x| x.widget_id)
-            .collect()
+    /// Indicates whether or not the `Widget` specified by `widget_id` accepts a drop of `payload`.
+    /// Hidden and disabled `Widget`s never accept drops.
+    pub fn accepts_drop(&mut self, widget_id: i32, payload: &DragPayload) -> bool {
+        !self.is_hidden(widget_id)
+            && self.is_enabled(widget_id)
+            && self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .accepts_drop(payload)
+    }
+
+    /// This function calls the `drop_entered` callback for the `Widget` specified by `widget_id`.
+    pub fn drop_entered(&mut self, widget_id: i32) {
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .drop_entered(&self.cache);
+    }
+
+    /// This function calls the `drop_exited` callback for the `Widget` specified by `widget_id`.
+    pub fn drop_exited(&mut self, widget_id: i32) {
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .drop_exited(&self.cache);
+    }
+
+    /// This function calls the `dropped` callback for the `Widget` specified by `widget_id`, if it
+    /// accepts the `payload`.  Returns `true` if the payload was delivered.
+    pub fn dropped(&mut self, widget_id: i32, payload: DragPayload, points: Vec<i32>) -> bool {
+        if !self.accepts_drop(widget_id, &payload) {
+            return false;
+        }
+
+        let points = self.to_local(widget_id, points);
+
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .dropped(&self.cache, payload, points);
+
+        true
     }
 
-    fn draw(&mut self, widget_id: i32, c: &mut Canvas<Window>) {
-        let parents_of_widget = self.get_children_of(widget_id);
+    /// Indicates whether or not the point at `x` and `y` lies within the bounds of the `Widget`
+    /// specified by `widget_id`, regardless of any `Widget`s drawn on top of it.
+    pub fn is_point_in_widget(&mut self, widget_id: i32, x: i32, y: i32) -> bool {
+        self.get_absolute_drawing_area(widget_id)
+            .contains_point((x, y))
+    }
+
+    /// Returns the ID of the `Widget` that currently has keyboard focus.  A `0` indicates that no
+    /// `Widget` has been explicitly focused.
+    pub fn get_focused_widget_id(&self) -> i32 {
+        self.focused_widget_id
+    }
 
-        if parents_of_widget.is_empty() {
+    /// Assigns keyboard focus to the `Widget` specified by `widget_id`.  The `Widget` losing focus
+    /// receives a `focus_lost` call, and the `Widget` receiving focus receives a `focus_gained`
+    /// call.  Hidden or disabled `Widget`s cannot receive focus; attempting to focus one leaves the
+    /// focus unchanged.  Setting the focus to `0` clears the focus.
+    pub fn set_focus(&mut self, widget_id: i32) {
+        if widget_id < 0 || widget_id as usize >= self.cache.len() {
             return;
         }
 
-        let top_level_rect = self.cache[0].widget.borrow_mut().get_drawing_area();
-        let mut needs_present = false;
-
-        for paint_id in &parents_of_widget {
-            let paint_widget = &mut self.cache[*paint_id as usize];
-            let is_hidden = paint_widget.widget.borrow_mut().get_config().is_hidden();
-            let is_enabled = paint_widget.widget.borrow_mut().get_config().is_enabled();
-            let is_invalidated = paint_widget.widget.borrow_mut().get_config().invalidated();
-            let widget_x = paint_widget.widget.borrow_mut().get_config().to_x(0);
-            let widget_y = paint_widget.widget.borrow_mut().get_config().to_y(0);
-            let widget_w = paint_widget
+        if widget_id == self.focused_widget_id {
+            return;
+        }
+
+        if widget_id != 0 && (self.is_hidden(widget_id) || !self.is_enabled(widget_id)) {
+            return;
+        }
+
+        let old_focus_id = self.focused_widget_id;
+
+        debug!(
+            target: "pushrod::cache",
+            "Focus changed: from={} to={}",
+            old_focus_id,
+            widget_id
+        );
+
+        self.focused_widget_id = widget_id;
+
+        if old_focus_id != 0 {
+            self.cache[old_focus_id as usize]
                 .widget
                 .borrow_mut()
-                .get_config()
-                .get_size(CONFIG_SIZE)[0];
-            let widget_h = paint_widget
+                .focus_lost(&self.cache);
+        }
+
+        if widget_id != 0 {
+            self.cache[widget_id as usize]
                 .widget
                 .borrow_mut()
-                .get_config()
-                .get_size(CONFIG_SIZE)[1];
+                .focus_gained(&self.cache);
+        }
+    }
 
-            eprintln!(
-                "Widget redraw: id={:?} hidden={} invalidated={}",
-                paint_id, is_hidden, is_invalidated
-            );
+    /// This function calls the `key_pressed` callback for the `Widget` that currently has focus.
+    pub fn key_pressed(&mut self, keycode: Keycode, keymod: Mod) {
+        let widget_id = self.focused_widget_id;
+
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .key_pressed(&self.cache, keycode, keymod);
+        }
+    }
+
+    /// This function calls the `key_released` callback for the `Widget` that currently has focus.
+    pub fn key_released(&mut self, keycode: Keycode, keymod: Mod) {
+        let widget_id = self.focused_widget_id;
+
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .key_released(&self.cache, keycode, keymod);
+        }
+    }
+
+    /// This function calls the `text_input` callback for the `Widget` that currently has focus.
+    pub fn text_input(&mut self, text: String) {
+        let widget_id = self.focused_widget_id;
+
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .text_input(&self.cache, text);
+        }
+    }
+
+    /// This function calls the `text_edited` callback for the `Widget` that currently has focus.
+    pub fn text_edited(&mut self, text: String, start: i32, length: i32) {
+        let widget_id = self.focused_widget_id;
+
+        if !self.is_hidden(widget_id) && self.is_enabled(widget_id) {
+            self.cache[widget_id as usize]
+                .widget
+                .borrow_mut()
+                .text_edited(&self.cache, text, start, length);
+        }
+    }
 
-            if !is_hidden && is_invalidated {
-                c.set_clip_rect(paint_widget.widget.borrow_mut().get_drawing_area());
-                paint_widget.widget.borrow_mut().draw(c);
-                paint_widget
-                    .widget
-                    .borrow_mut()
-                    .get_config()
-                    .set_invalidate(false);
-                c.set_clip_rect(top_level_rect);
-
-                needs_present = true;
+    /// Applies a `WidgetMessage` that was posted through a `WidgetSender` to its target `Widget`,
+    /// and invalidates the `Widget` so that it is redrawn.  Messages for a `Widget` that cannot
+    /// be found are discarded.
+    pub fn apply_message(&mut self, message: WidgetMessage) {
+        let target = match &message {
+            WidgetMessage::Update(target, _) => target,
+            WidgetMessage::Config(target, _, _) => target,
+            WidgetMessage::Value(target, _, _) => target,
+        };
+
+        let widget_id = match target {
+            WidgetTarget::Id(id) => self.get_widget_index(*id).unwrap_or(-1),
+            WidgetTarget::Name(name) => {
+                match self.cache.iter().find(|x| x.get_widget_name() == *name) {
+                    Some(x) => x.get_widget_id(),
+                    None => -1,
+                }
             }
+        };
+
+        if widget_id == -1 {
+            warn!(
+                target: "pushrod::cache",
+                "Discarding message for unknown widget: {:?}",
+                target
+            );
+            return;
+        }
+
+        let mut widget = self.cache[widget_id as usize].widget.borrow_mut();
+
+        match message {
+            WidgetMessage::Update(_, f) => f(&mut **widget, &self.cache),
+            WidgetMessage::Config(_, key, value) => apply_config(&mut **widget, key, value),
+            WidgetMessage::Value(_, key, value) => widget.set_config_value(key, value),
+        }
+
+        widget.get_config().set_invalidate(true);
+    }
+
+    /// Binds the configuration value `key` of the `Widget` specified by `widget_id` to the
+    /// `observable`.  The value of the `observable` is copied to the `Widget` by the next call to
+    /// `sync_bindings`, and whenever it changes after that.  With `BindingMode::TwoWay`, changes
+    /// that the `Widget` makes to the value are copied back to the `observable`.  The binding is
+    /// removed when the `Widget` is removed.  Returns an error if the `Widget` does not exist.
+    pub fn bind<T: ConfigValue + Clone + PartialEq + 'static>(
+        &mut self,
+        widget_id: WidgetId,
+        key: ConfigKey<T>,
+        observable: &Observable<T>,
+        mode: BindingMode,
+    ) -> Result<(), String> {
+        if self.get_widget_index(widget_id).is_none() {
+            return Err(format!("Widget does not exist: id={:?}", widget_id));
+        }
+
+        self.bindings.push(Box::new(ConfigBinding::new(
+            widget_id,
+            key,
+            observable.clone(),
+            mode,
+        )));
+
+        Ok(())
+    }
+
+    /// Removes all bindings of the `Widget` specified by `widget_id`.
+    pub fn unbind(&mut self, widget_id: WidgetId) {
+        self.bindings.retain(|x| x.widget_id() != widget_id);
+    }
 
-            if *paint_id != widget_id {
-                self.draw(*paint_id, c);
+    /// Copies changes between bound `Widget`s and `Observable`s.  Changes that `Widget`s made are
+    /// copied to their `Observable`s first, while no `Widget` is borrowed, so that subscribers of
+    /// the `Observable`s can access any `Widget`.  Changes to the `Observable`s are then copied to
+    /// their `Widget`s, which are invalidated.  This is called by the `Engine` on every `tick`.
+    pub fn sync_bindings(&mut self) {
+        let mut bindings = std::mem::take(&mut self.bindings);
+
+        bindings.retain(|x| self.get_widget_index(x.widget_id()).is_some());
+
+        for binding in bindings.iter_mut() {
+            if let Some(index) = self.get_widget_index(binding.widget_id()) {
+                binding.read_widget(&mut **self.cache[index as usize].widget.borrow_mut());
             }
+        }
 
-            if !is_enabled {
-                c.set_draw_color(Color::RGBA(0, 0, 0, 128));
-                c.draw_rect(Rect::new(widget_x, widget_y, widget_w, widget_h))
-                    .unwrap();
+        for binding in bindings.iter_mut() {
+            binding.update_model();
+        }
+
+        for binding in bindings.iter_mut() {
+            if let Some(index) = self.get_widget_index(binding.widget_id()) {
+                binding.update_widget(&mut **self.cache[index as usize].widget.borrow_mut());
             }
         }
 
-        if needs_present {
-            eprintln!("Presenting canvas.");
-            c.present();
+        bindings.append(&mut self.bindings);
+        self.bindings = bindings;
+    }
+
+    /// This function calls the `tick` method on all registered `Widget`s in the cache.  The purpose
+    /// for the `tick` is to indicate that a drawing loop is about to occur, and the `Widget` can
+    /// update itself as necessary beforehand.
+    pub fn tick(&mut self) {
+        let cache_size = self.cache.len();
+
+        for i in 0..cache_size {
+            if !self.is_hidden(i as i32) {
+                self.cache[i].widget.borrow_mut().tick(&self.cache);
+            }
         }
     }
 
-    fn is_hidden(&self, widget_id: i32) -> bool {
-        self.cache[widget_id as usize]
-            .widget
-            .borrow_mut()
-            .get_config()
-            .is_hidden()
+    /// Indicates whether or not any `Widget` in the cache is invalidated, or any area of the screen
+    /// is damaged, and needs to be redrawn.
+    pub fn is_invalidated(&mut self) -> bool {
+        !self.pending_damage.is_empty()
+            || self
+                .cache
+                .iter()
+                .any(|x| x.widget.borrow_mut().get_config().invalidated())
     }
 
-    fn is_enabled(&self, widget_id: i32) -> bool {
-        self.cache[widget_id as usize]
-            .widget
-            .borrow_mut()
-            .get_config()
-            .is_enabled()
+    /// Indicates whether or not any visible `Widget` in the cache needs to receive `tick` calls,
+    /// such as an enabled timer, or a running animation.
+    pub fn needs_tick(&mut self) -> bool {
+        let cache_size = self.cache.len();
+
+        for i in 0..cache_size {
+            if !self.is_hidden(i as i32) && self.cache[i].widget.borrow_mut().needs_tick() {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Marks every `Widget` in the cache as invalidated, so that the next call to `draw_loop`
+    /// repaints the entire display list.
+    pub fn invalidate_all(&mut self) {
+        for container in &self.cache {
+            container
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidate(true);
+        }
+    }
+
+    /// This function performs the draw loop for all of the `Widget`s stored in the `cache`.  Each
+    /// `Widget` receives a mutable reference to the `RenderCanvas` so that the `Widget` can be
+    /// drawn during the draw loop of the `Engine`.  The canvas can be a `Canvas<Window>` to draw
+    /// on the screen, or a `Canvas<Surface>` to draw to memory.  This `draw_loop` function
+    /// automatically clips the screen area so that the `Widget` cannot draw outside of its bounds.
+    ///
+    /// Only the damaged areas of the screen are redrawn, and every `Widget` that intersects a
+    /// damaged area is redrawn, clipped to it.  Returns `true` if any area was redrawn, indicating
+    /// that the canvas needs to be presented.  The redrawn areas can be retrieved with `get_damage`.
+    pub fn draw_loop(&mut self, canvas: &mut dyn RenderCanvas) -> bool {
+        let visible_areas = self.get_visible_areas();
+        let mut damage = std::mem::take(&mut self.pending_damage);
+
+        for (container, visible_area) in self.cache.iter().zip(&visible_areas) {
+            let invalidated = container.widget.borrow_mut().get_config().invalidated();
+
+            if invalidated || container.painted_area != *visible_area {
+                damage.extend(container.painted_area);
+                damage.extend(*visible_area);
+            }
+        }
+
+        self.damage = coalesce_damage(damage);
+        self.update_render_caches(&visible_areas);
+
+        for damage_rect in self.damage.clone() {
+            trace!(target: "pushrod::draw", "Repainting damage: {:?}", damage_rect);
+
+            self.draw(0, canvas, damage_rect, &visible_areas);
+        }
+
+        for (container, visible_area) in self.cache.iter_mut().zip(&visible_areas) {
+            container.painted_area = *visible_area;
+            container
+                .widget
+                .borrow_mut()
+                .get_config()
+                .set_invalidate(false);
+        }
+
+        if self.damage.is_empty() {
+            return false;
+        }
+
+        let top_level_rect = self.cache[0].widget.borrow_mut().get_drawing_area();
+
+        canvas.set_clip_rect(Some(top_level_rect));
+
+        true
+    }
+
+    /// Draws every visible `Widget` to the `canvas`, regardless of whether or not it has been
+    /// invalidated.  Unlike `draw_loop`, this does not change the invalidation state of any
+    /// `Widget`, or the areas that are recorded as painted or damaged, so it can be used to take a
+    /// snapshot of the display list to another canvas without affecting the next `draw_loop`.
+    pub fn draw_all(&mut self, canvas: &mut dyn RenderCanvas) {
+        let visible_areas = self.get_visible_areas();
+        let top_level_rect = self.cache[0].widget.borrow_mut().get_drawing_area();
+
+        self.update_render_caches(&visible_areas);
+        self.draw(0, canvas, top_level_rect, &visible_areas);
+
+        canvas.set_clip_rect(Some(top_level_rect));
+    }
+
+    /// Returns the `ResourceCache` that `Widget`s load their fonts and images from while they are
+    /// drawn.
+    pub fn get_resources(&mut self) -> &mut ResourceCache {
+        &mut self.resources
+    }
+
+    /// Returns the errors that `Widget`s returned while they were drawn since the last call to this
+    /// function, along with the `WidgetId` of each `Widget`, and removes them from the cache.  Each
+    /// `Widget` is reported at most once per call, even if it failed to draw more than once.
+    pub fn take_errors(&mut self) -> Vec<(WidgetId, PushrodError)> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Returns the areas of the screen, in `Window` coordinates, that were redrawn by the last call
+    /// to `draw_loop`.  Overlapping areas are merged, so no two areas intersect.
+    pub fn get_damage(&self) -> &[Rect] {
+        &self.damage
+    }
+
+    // Private functions
+
+    /// Returns the children of the `Widget` specified by `widget_id` in drawing order: the layer
+    /// first, then the stacking order within the layer.  The top-level `Widget` is its own parent,
+    /// so it is always returned first among its children.
+    fn get_children_of(&mut self, widget_id: i32) -> Vec<i32> {
+        let mut children: Vec<&WidgetContainer> = self
+            .cache
+            .iter()
+            .filter(|x| x.parent_id == widget_id)
+            .collect();
+
+        children.sort_by_key(|x| (x.widget_id != widget_id, x.layer, x.z_order));
+        children.iter().map(|x| x.widget_id).collect()
+    }
+
+    /// Returns the siblings of the `Widget` specified by `widget_id` that are in the same layer,
+    /// including the `Widget` itself, in drawing order.
+    fn get_layer_siblings_of(&mut self, widget_id: i32) -> Vec<i32> {
+        let parent_id = self.cache[widget_id as usize].parent_id;
+        let layer = self.cache[widget_id as usize].layer;
+
+        self.get_children_of(parent_id)
+            .into_iter()
+            .filter(|x| *x != parent_id && self.cache[*x as usize].layer == layer)
+            .collect()
+    }
+
+    fn get_restack_index(&self, widget_id: WidgetId) -> Result<i32, String> {
+        match self.get_widget_index(widget_id) {
+            Some(0) => Err(String::from("The top-level widget cannot be restacked")),
+            Some(index) => Ok(index),
+            None => Err(format!("Widget does not exist: id={:?}", widget_id)),
+        }
+    }
+
+    fn swap_z_order(&mut self, widget_id: i32, other_id: i32) {
+        let z_order = self.cache[widget_id as usize].z_order;
+        let other_z_order = self.cache[other_id as usize].z_order;
+
+        self.set_z_order(widget_id, other_z_order);
+        self.set_z_order(other_id, z_order);
+    }
+
+    /// Changes the stacking order of the `Widget` specified by `widget_id`, and invalidates it, so
+    /// that its area is redrawn in the new order.
+    fn set_z_order(&mut self, widget_id: i32, z_order: i32) {
+        self.cache[widget_id as usize].z_order = z_order;
+        self.geometry_changed.set(true);
+        self.cache[widget_id as usize]
+            .widget
+            .borrow_mut()
+            .get_config()
+            .set_invalidate(true);
+    }
+
+    fn push_widget(
+        &mut self,
+        mut widget: Box<dyn Widget>,
+        widget_name: String,
+        parent_id: i32,
+        layer: WidgetLayer,
+    ) -> WidgetId {
+        let origin = widget.get_config().try_get(KEY_ORIGIN).unwrap_or_default();
+        let widget_id = self.cache.len();
+        let slot = match self.free_slots.pop() {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(WidgetSlot::default());
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let id = WidgetId {
+            slot,
+            generation: self.slots[slot as usize].generation,
+        };
+
+        self.slots[slot as usize].index = Some(widget_id);
+        self.next_z_order += 1;
+
+        widget
+            .get_config()
+            .set_geometry_listener(self.geometry_changed.clone());
+        self.geometry_changed.set(true);
+
+        debug!(
+            target: "pushrod::cache",
+            "Widget added: id={} name={} parent={}",
+            widget_id,
+            widget_name,
+            parent_id
+        );
+
+        let mut container = WidgetContainer::new(
+            widget,
+            widget_name.clone(),
+            origin,
+            widget_id as i32,
+            parent_id,
+            id,
+        );
+
+        container.layer = layer;
+        container.z_order = self.next_z_order;
+        self.cache.push(container);
+
+        id
+    }
+
+    /// Returns the offset of the coordinate space of the `Widget` specified by `widget_id`, which
+    /// is the sum of the origins of all of its ancestors.
+    fn get_offset_of(&self, widget_id: i32) -> (i32, i32) {
+        let mut offset_x = 0;
+        let mut offset_y = 0;
+        let mut parent_id = self.cache[widget_id as usize].parent_id;
+
+        while parent_id != 0 {
+            let origin = self.cache[parent_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .try_get(KEY_ORIGIN)
+                .unwrap_or_default();
+
+            offset_x += origin[0];
+            offset_y += origin[1];
+            parent_id = self.cache[parent_id as usize].parent_id;
+        }
+
+        (offset_x, offset_y)
+    }
+
+    /// Translates `points` from `Window` coordinates to the coordinate space of the `Widget`
+    /// specified by `widget_id`.
+    fn to_local(&self, widget_id: i32, points: Vec<i32>) -> Vec<i32> {
+        let (offset_x, offset_y) = self.get_offset_of(widget_id);
+
+        vec![points[0] - offset_x, points[1] - offset_y]
+    }
+
+    /// Rebuilds the `SpatialIndex` from the current areas of all `Widget`s.  Each `Widget` is
+    /// indexed with its area clipped to the areas of its ancestors, and ranked in drawing order.
+    fn rebuild_spatial_index(&mut self) {
+        let mut children: Vec<Vec<i32>> = vec![Vec::new(); self.cache.len()];
+
+        for container in &self.cache {
+            if container.widget_id != container.parent_id {
+                children[container.parent_id as usize].push(container.widget_id);
+            }
+        }
+
+        for siblings in children.iter_mut() {
+            siblings.sort_by_key(|x| {
+                let container = &self.cache[*x as usize];
+
+                (container.layer, container.z_order)
+            });
+        }
+
+        let mut rank = 0;
+
+        self.spatial_index.clear();
+        self.index_children_of(&children, 0, (0, 0), None, &mut rank);
+    }
+
+    fn index_children_of(
+        &mut self,
+        children: &[Vec<i32>],
+        parent_id: i32,
+        offset: (i32, i32),
+        clip: Option<Rect>,
+        rank: &mut usize,
+    ) {
+        for child_id in &children[parent_id as usize] {
+            let area = self.cache[*child_id as usize]
+                .widget
+                .borrow_mut()
+                .get_drawing_area();
+            let absolute_area = Rect::new(
+                area.x() + offset.0,
+                area.y() + offset.1,
+                area.width(),
+                area.height(),
+            );
+            let clipped_area = match clip {
+                Some(clip) => absolute_area.intersection(clip),
+                None => Some(absolute_area),
+            };
+
+            // Children are only found inside the area of their parent, so if nothing of this
+            // widget is visible, neither are its children.
+            if let Some(clipped_area) = clipped_area {
+                *rank += 1;
+                self.spatial_index.insert(*child_id, clipped_area, *rank);
+                self.index_children_of(
+                    children,
+                    *child_id,
+                    (absolute_area.x(), absolute_area.y()),
+                    Some(clipped_area),
+                    rank,
+                );
+            }
+        }
+    }
+
+    /// Returns the area of each `Widget` that is visible on the screen, in `Window` coordinates,
+    /// clipped to the areas of its ancestors.  `Widget`s that are hidden, or that are entirely
+    /// outside of the areas of their ancestors, have no visible area.
+    fn get_visible_areas(&mut self) -> Vec<Option<Rect>> {
+        let mut visible_areas = vec![None; self.cache.len()];
+        let top_level_rect = self.cache[0].widget.borrow_mut().get_drawing_area();
+
+        if !self.cache[0].widget.borrow_mut().get_config().is_hidden() {
+            visible_areas[0] = Some(top_level_rect);
+        }
+
+        self.collect_visible_areas(0, (0, 0), top_level_rect, &mut visible_areas);
+
+        visible_areas
+    }
+
+    fn collect_visible_areas(
+        &mut self,
+        parent_id: i32,
+        offset: (i32, i32),
+        clip: Rect,
+        visible_areas: &mut [Option<Rect>],
+    ) {
+        for child_id in self.get_children_of(parent_id) {
+            if child_id == parent_id {
+                continue;
+            }
+
+            let mut widget = self.cache[child_id as usize].widget.borrow_mut();
+
+            // Children of a hidden widget are hidden as well, so the entire subtree is skipped.
+            if widget.get_config().is_hidden() {
+                continue;
+            }
+
+            let area = widget.get_drawing_area();
+            let absolute_area = Rect::new(
+                area.x() + offset.0,
+                area.y() + offset.1,
+                area.width(),
+                area.height(),
+            );
+
+            drop(widget);
+
+            if let Some(visible_area) = absolute_area.intersection(clip) {
+                visible_areas[child_id as usize] = Some(visible_area);
+                self.collect_visible_areas(
+                    child_id,
+                    (absolute_area.x(), absolute_area.y()),
+                    visible_area,
+                    visible_areas,
+                );
+            }
+        }
+    }
+
+    /// Renders every visible, render cached `Widget` that is invalidated, or whose size changed,
+    /// into its render cache.  The render caches of `Widget`s that opted out are released.
+    fn update_render_caches(&mut self, visible_areas: &[Option<Rect>]) {
+        for (container, visible_area) in self.cache.iter_mut().zip(visible_areas) {
+            let mut widget = container.widget.borrow_mut();
+
+            if !widget.get_config().is_render_cached() {
+                container.render_cache = None;
+                continue;
+            }
+
+            let area = widget.get_drawing_area();
+            let is_stale = match &container.render_cache {
+                Some(surface) => {
+                    widget.get_config().invalidated()
+                        || surface.width() != area.width()
+                        || surface.height() != area.height()
+                }
+                None => true,
+            };
+
+            if visible_area.is_none() || !is_stale {
+                continue;
+            }
+
+            // A widget that fails to draw is cached as a placeholder, so that its error is only
+            // reported again once it is redrawn.
+            container.render_cache = match render_widget(&mut **widget, area, &mut self.resources) {
+                Ok((surface, draw_error)) => {
+                    if let Some(e) = draw_error {
+                        push_error(&mut self.errors, container.id, e);
+                    }
+
+                    Some(Rc::new(surface))
+                }
+                Err(e) => {
+                    warn!(
+                        target: "pushrod::draw",
+                        "Unable to render widget to cache: id={} error={}",
+                        container.widget_id,
+                        e
+                    );
+                    None
+                }
+            };
+        }
+    }
+
+    /// Redraws the children of the `Widget` specified by `widget_id`, and their children, that
+    /// intersect the `damage` area, in drawing order.  Each `Widget` is clipped to the part of its
+    /// visible area that is damaged.
+    fn draw(
+        &mut self,
+        widget_id: i32,
+        c: &mut dyn RenderCanvas,
+        damage: Rect,
+        visible_areas: &[Option<Rect>],
+    ) {
+        for paint_id in self.get_children_of(widget_id) {
+            // The visible area of a child lies within that of its parent, so if the parent is
+            // not damaged, neither are its children.
+            let paint_area = match visible_areas[paint_id as usize] {
+                Some(visible_area) => match visible_area.intersection(damage) {
+                    Some(paint_area) => paint_area,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            trace!(
+                target: "pushrod::draw",
+                "Widget redraw: id={:?} area={:?}",
+                paint_id,
+                paint_area
+            );
+
+            let (offset_x, offset_y) = self.get_offset_of(paint_id);
+            let widget_area = self.get_absolute_drawing_area(paint_id);
+
+            c.set_clip_rect(Some(paint_area));
+
+            let container = &self.cache[paint_id as usize];
+
+            match &container.render_cache {
+                Some(surface) => {
+                    if let Err(e) = c.copy_render_cache(container.id, surface, None, widget_area) {
+                        warn!(
+                            target: "pushrod::draw",
+                            "Unable to copy render cache: id={} error={}",
+                            paint_id,
+                            e
+                        );
+                    }
+                }
+
+                None => {
+                    let mut offset_canvas =
+                        OffsetCanvas::new(c, offset_x, offset_y, Some(paint_area));
+                    let container = &self.cache[paint_id as usize];
+                    let mut widget = container.widget.borrow_mut();
+
+                    offset_canvas.set_resources(&mut self.resources);
+
+                    if let Err(e) = widget.try_draw(&mut offset_canvas) {
+                        draw_placeholder(&mut offset_canvas, widget.get_drawing_area());
+                        push_error(&mut self.errors, container.id, e);
+                    }
+                }
+            }
+
+            if paint_id != widget_id {
+                self.draw(paint_id, c, damage, visible_areas);
+            }
+
+            let is_enabled = self.cache[paint_id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .is_enabled();
+
+            if !is_enabled {
+                c.set_clip_rect(Some(paint_area));
+                c.set_draw_color(Color::RGBA(0, 0, 0, 128));
+
+                if let Err(e) = c.draw_rect(widget_area) {
+                    warn!(
+                        target: "pushrod::draw",
+                        "Disabled outline could not be drawn: id={} error={}",
+                        paint_id,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Indicates whether or not the `Widget` specified by `widget_id`, or any of its ancestors, is
+    /// hidden.  The top-level `Widget` is not considered an ancestor, as `Widget`s added with
+    /// `add_widget` have no parent.
+    fn is_hidden(&self, widget_id: i32) -> bool {
+        let mut id = widget_id;
+
+        loop {
+            if self.cache[id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .is_hidden()
+            {
+                return true;
+            }
+
+            id = self.cache[id as usize].parent_id;
+
+            if id == 0 {
+                return false;
+            }
+        }
+    }
+
+    /// Indicates whether or not the `Widget` specified by `widget_id`, and all of its ancestors,
+    /// are enabled.  As with `is_hidden`, the top-level `Widget` is not considered an ancestor.
+    fn is_enabled(&self, widget_id: i32) -> bool {
+        let mut id = widget_id;
+
+        loop {
+            if !self.cache[id as usize]
+                .widget
+                .borrow_mut()
+                .get_config()
+                .is_enabled()
+            {
+                return false;
+            }
+
+            id = self.cache[id as usize].parent_id;
+
+            if id == 0 {
+                return true;
+            }
+        }
+    }
+}
+
+/// Merges overlapping damage areas, so that no area of the screen is redrawn more than once.
+/// Areas that share an edge are merged as well, so that they are redrawn in a single pass.
+fn coalesce_damage(mut damage: Vec<Rect>) -> Vec<Rect> {
+    let mut merged: Vec<Rect> = Vec::new();
+
+    while let Some(mut rect) = damage.pop() {
+        while let Some(index) = merged.iter().position(|x| touches(*x, rect)) {
+            rect = rect.union(merged.swap_remove(index));
+        }
+
+        merged.push(rect);
+    }
+
+    merged
+}
+
+/// Indicates whether or not the areas `a` and `b` overlap, or share an edge.  Areas that only
+/// meet at a corner do not touch.
+fn touches(a: Rect, b: Rect) -> bool {
+    let overlaps_x = a.left() < b.right() && b.left() < a.right();
+    let overlaps_y = a.top() < b.bottom() && b.top() < a.bottom();
+    let meets_x = a.left() <= b.right() && b.left() <= a.right();
+    let meets_y = a.top() <= b.bottom() && b.top() <= a.bottom();
+
+    (overlaps_x && meets_y) || (overlaps_y && meets_x)
+}
+
+/// Adds the `error` returned by the `Widget` specified by `id` to `errors`, unless an error has
+/// already been recorded for that `Widget`.
+fn push_error(errors: &mut Vec<(WidgetId, PushrodError)>, id: WidgetId, error: PushrodError) {
+    if !errors.iter().any(|(x, _)| *x == id) {
+        errors.push((id, error));
+    }
+}
+
+/// Draws `widget`, whose drawing area is `area`, into a new transparent `Surface` of the same size.
+/// If the `Widget` returns an error, a placeholder is drawn instead, and the error is returned
+/// along with the `Surface`.  Returns an error if the `Surface` could not be created.
+fn render_widget(
+    widget: &mut dyn Widget,
+    area: Rect,
+    resources: &mut ResourceCache,
+) -> Result<(Surface<'static>, Option<PushrodError>), String> {
+    let surface = Surface::new(area.width(), area.height(), PixelFormatEnum::ARGB8888)?;
+    let mut canvas = surface.into_canvas()?;
+
+    canvas.set_draw_color(Color::RGBA(0, 0, 0, 0));
+    canvas.clear();
+
+    let draw_error = {
+        let mut offset_canvas = OffsetCanvas::new(&mut canvas, -area.x(), -area.y(), None);
+
+        offset_canvas.set_resources(resources);
+
+        match widget.try_draw(&mut offset_canvas) {
+            Ok(()) => None,
+            Err(e) => {
+                draw_placeholder(&mut offset_canvas, area);
+                Some(e)
+            }
+        }
+    };
+
+    Ok((canvas.into_surface(), draw_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::widget::BaseWidget;
+    use std::cell::RefMut;
+
+    fn new_cache() -> WidgetCache {
+        let mut cache = WidgetCache::new();
+
+        cache.add_widget(
+            Box::new(BaseWidget::new(0, 0, 400, 300)),
+            String::from("base"),
+        );
+
+        cache
+    }
+
+    fn add_box(cache: &mut WidgetCache, name: &str, x: i32, y: i32, w: u32, h: u32) -> i32 {
+        let id = cache.add_widget(Box::new(BaseWidget::new(x, y, w, h)), String::from(name));
+
+        cache.get_widget_index(id).unwrap()
+    }
+
+    fn widget_of(cache: &WidgetCache, widget_id: i32) -> RefMut<'_, Box<dyn Widget>> {
+        cache.get_widgets()[widget_id as usize].widget.borrow_mut()
+    }
+
+    #[test]
+    fn find_widget_returns_top_most_of_overlapping_widgets() {
+        let mut cache = new_cache();
+        let lower = add_box(&mut cache, "lower", 10, 10, 100, 100);
+        let upper = add_box(&mut cache, "upper", 50, 50, 100, 100);
+
+        assert_eq!(cache.find_widget(20, 20), lower);
+        assert_eq!(cache.find_widget(75, 75), upper);
+        assert_eq!(cache.find_widget(300, 250), 0);
+
+        let lower_id = cache.get_id_of(lower).unwrap();
+
+        cache.bring_to_front(lower_id).unwrap();
+
+        assert_eq!(cache.find_widget(75, 75), lower);
+        assert_eq!(cache.find_widget(125, 125), upper);
+    }
+
+    #[test]
+    fn find_widget_prefers_overlays() {
+        let mut cache = new_cache();
+        let overlay_id = cache.add_overlay(
+            Box::new(BaseWidget::new(0, 0, 100, 100)),
+            String::from("overlay"),
+        );
+        let overlay = cache.get_widget_index(overlay_id).unwrap();
+        let normal = add_box(&mut cache, "normal", 0, 0, 100, 100);
+
+        assert_eq!(cache.find_widget(50, 50), overlay);
+
+        widget_of(&cache, overlay).get_config().hide();
+
+        assert_eq!(cache.find_widget(50, 50), normal);
+    }
+
+    #[test]
+    fn find_widget_skips_hidden_widgets_and_their_children() {
+        let mut cache = new_cache();
+        let lower = add_box(&mut cache, "lower", 0, 0, 200, 200);
+        let parent = add_box(&mut cache, "parent", 0, 0, 100, 100);
+        let parent_id = cache.get_id_of(parent).unwrap();
+        let child_id = cache
+            .add_child(
+                parent_id,
+                Box::new(BaseWidget::new(10, 10, 20, 20)),
+                String::from("child"),
+            )
+            .unwrap();
+        let child = cache.get_widget_index(child_id).unwrap();
+
+        assert_eq!(cache.find_widget(15, 15), child);
+        assert_eq!(cache.find_widget(50, 50), parent);
+
+        widget_of(&cache, parent).get_config().hide();
+
+        assert_eq!(cache.find_widget(15, 15), lower);
+        assert_eq!(cache.find_widget(50, 50), lower);
+
+        widget_of(&cache, parent).get_config().show();
+
+        assert_eq!(cache.find_widget(15, 15), child);
+    }
+
+    #[test]
+    fn find_widget_clips_children_to_their_parent() {
+        let mut cache = new_cache();
+        let parent = add_box(&mut cache, "parent", 100, 100, 50, 50);
+        let parent_id = cache.get_id_of(parent).unwrap();
+        let child_id = cache
+            .add_child(
+                parent_id,
+                Box::new(BaseWidget::new(25, 25, 100, 100)),
+                String::from("child"),
+            )
+            .unwrap();
+        let child = cache.get_widget_index(child_id).unwrap();
+
+        // The child is positioned relative to its parent.
+        assert_eq!(cache.find_widget(130, 130), child);
+        assert_eq!(cache.find_widget(110, 110), parent);
+
+        // Outside of the parent, the child cannot be found.
+        assert_eq!(cache.find_widget(175, 175), 0);
+    }
+
+    #[test]
+    fn find_widget_follows_moved_and_resized_widgets() {
+        let mut cache = new_cache();
+        let widget = add_box(&mut cache, "widget", 10, 10, 50, 50);
+
+        assert_eq!(cache.find_widget(20, 20), widget);
+
+        widget_of(&cache, widget).set_origin(vec![200, 100]);
+
+        assert_eq!(cache.find_widget(20, 20), 0);
+        assert_eq!(cache.find_widget(210, 110), widget);
+        assert_eq!(cache.find_widget(270, 110), 0);
+
+        widget_of(&cache, widget).set_size(vec![100, 50]);
+
+        assert_eq!(cache.find_widget(270, 110), widget);
+    }
+
+    fn names_of(cache: &WidgetCache) -> Vec<String> {
+        cache
+            .get_widgets()
+            .iter()
+            .map(|x| x.get_widget_name())
+            .collect()
+    }
+
+    #[test]
+    fn remove_widget_removes_the_whole_subtree() {
+        let mut cache = new_cache();
+        let parent = add_box(&mut cache, "parent", 0, 0, 200, 200);
+        let parent_id = cache.get_id_of(parent).unwrap();
+        let sibling = add_box(&mut cache, "sibling", 250, 0, 100, 100);
+        let sibling_id = cache.get_id_of(sibling).unwrap();
+        let child_id = cache
+            .add_child(
+                parent_id,
+                Box::new(BaseWidget::new(10, 10, 100, 100)),
+                String::from("child"),
+            )
+            .unwrap();
+        let grandchild_id = cache
+            .add_child(
+                child_id,
+                Box::new(BaseWidget::new(10, 10, 50, 50)),
+                String::from("grandchild"),
+            )
+            .unwrap();
+
+        cache.remove_widget(parent_id).unwrap();
+
+        assert_eq!(names_of(&cache), vec!["base", "sibling"]);
+        assert_eq!(cache.get_widget_index(parent_id), None);
+        assert_eq!(cache.get_widget_index(child_id), None);
+        assert_eq!(cache.get_widget_index(grandchild_id), None);
+
+        // The remaining widget is renumbered, but its ID still resolves to it.
+        assert_eq!(cache.get_widget_index(sibling_id), Some(1));
+        assert_eq!(cache.find_widget(20, 20), 0);
+        assert_eq!(cache.find_widget(260, 10), 1);
+    }
+
+    #[test]
+    fn remove_widget_rejects_the_top_level_and_stale_ids() {
+        let mut cache = new_cache();
+        let top_level_id = cache.get_id_of(0).unwrap();
+        let widget = add_box(&mut cache, "widget", 0, 0, 100, 100);
+        let widget_id = cache.get_id_of(widget).unwrap();
+
+        assert!(cache.remove_widget(top_level_id).is_err());
+        assert!(cache.remove_widget(widget_id).is_ok());
+        assert!(cache.remove_widget(widget_id).is_err());
+        assert_eq!(names_of(&cache), vec!["base"]);
+    }
+
+    #[test]
+    fn removed_ids_do_not_resolve_after_their_slot_is_reused() {
+        let mut cache = new_cache();
+        let old = add_box(&mut cache, "old", 0, 0, 100, 100);
+        let old_id = cache.get_id_of(old).unwrap();
+
+        cache.remove_widget(old_id).unwrap();
+
+        let new = add_box(&mut cache, "new", 0, 0, 100, 100);
+        let new_id = cache.get_id_of(new).unwrap();
+
+        // The slot is reused with a new generation, so the old ID is stale.
+        assert_eq!(new_id.slot, old_id.slot);
+        assert_ne!(new_id.generation, old_id.generation);
+        assert_eq!(cache.get_widget_index(old_id), None);
+        assert_eq!(cache.get_widget_index(new_id), Some(new));
+        assert!(cache
+            .add_child(
+                old_id,
+                Box::new(BaseWidget::new(0, 0, 10, 10)),
+                String::from("orphan")
+            )
+            .is_err());
+        assert!(cache.remove_widget(old_id).is_err());
+        assert_eq!(names_of(&cache), vec!["base", "new"]);
+    }
+
+    fn sorted(mut rects: Vec<Rect>) -> Vec<Rect> {
+        rects.sort_by_key(|x| (x.x(), x.y(), x.width(), x.height()));
+        rects
+    }
+
+    #[test]
+    fn coalesce_damage_merges_overlapping_areas() {
+        let damage = vec![
+            Rect::new(0, 0, 20, 20),
+            Rect::new(10, 10, 20, 20),
+            Rect::new(100, 100, 10, 10),
+        ];
+
+        assert_eq!(
+            sorted(coalesce_damage(damage)),
+            vec![Rect::new(0, 0, 30, 30), Rect::new(100, 100, 10, 10)]
+        );
+    }
+
+    #[test]
+    fn coalesce_damage_merges_chains_of_areas() {
+        // The last area joins the first two, which do not touch each other.
+        let damage = vec![
+            Rect::new(0, 0, 10, 10),
+            Rect::new(20, 0, 10, 10),
+            Rect::new(5, 0, 20, 10),
+        ];
+
+        assert_eq!(coalesce_damage(damage), vec![Rect::new(0, 0, 30, 10)]);
+    }
+
+    #[test]
+    fn coalesce_damage_merges_adjacent_areas() {
+        let damage = vec![
+            Rect::new(0, 0, 10, 10),
+            Rect::new(10, 0, 10, 10),
+            Rect::new(0, 10, 20, 5),
+        ];
+
+        assert_eq!(coalesce_damage(damage), vec![Rect::new(0, 0, 20, 15)]);
+    }
+
+    #[test]
+    fn coalesce_damage_keeps_areas_that_meet_at_a_corner() {
+        let damage = vec![Rect::new(0, 0, 10, 10), Rect::new(10, 10, 10, 10)];
+
+        assert_eq!(
+            sorted(coalesce_damage(damage)),
+            vec![Rect::new(0, 0, 10, 10), Rect::new(10, 10, 10, 10)]
+        );
+    }
+
+    #[test]
+    fn coalesce_damage_of_nothing_is_nothing() {
+        assert!(coalesce_damage(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn draw_loop_without_damage_draws_nothing() {
+        let mut cache = new_cache();
+        let mut surface_canvas = Surface::new(400, 300, PixelFormatEnum::ARGB8888)
+            .unwrap()
+            .into_canvas()
+            .unwrap();
+
+        add_box(&mut cache, "widget", 10, 10, 50, 50);
+
+        assert!(cache.draw_loop(&mut surface_canvas));
+        assert!(!cache.is_invalidated());
+        assert!(!cache.draw_loop(&mut surface_canvas));
+        assert!(cache.get_damage().is_empty());
     }
 }
@@ -13,9 +13,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::render::resources::DEFAULT_FONT;
 use crate::render::{Points, Size};
+use log::{trace, warn};
 use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::ttf::FontStyle;
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
 
 /// `Widget` Base `Color` key for `colors` `HashMap`.  This is the base fill color of a `Widget`
 /// that is in an unselected state.  This stored as a `Config::Color`.
@@ -44,8 +53,8 @@ pub const CONFIG_COLOR_SELECTED: u8 = 4;
 /// etc.  This stored as a `Config::Color`.
 pub const CONFIG_COLOR_SECONDARY: u8 = 5;
 
-/// `Widget` configuration to store its origin on the screen.  This is a `Config::Points` object in the
-/// config.
+/// `Widget` configuration to store its origin on the screen.  This is a `Config::Points` object in
+/// the config.
 pub const CONFIG_ORIGIN: u8 = 6;
 
 /// `Widget` configuration that stores the size of the `Widget`.  This is a `Config::Size` object
@@ -73,9 +82,60 @@ pub const CONFIG_FONT_SIZE: u8 = 12;
 /// `PushButtonWidget` selected state.  This is stored as a `Config::Toggle` value.
 pub const CONFIG_SELECTED_STATE: u8 = 13;
 
+/// `Widget` font, used to display text on the screen.  Setting it on a `TextWidget`, or on a
+/// `Widget` that draws its text with one, replaces the font given when it was created.  This is
+/// stored as a `Config::Font` value.
+pub const CONFIG_FONT: u8 = 14;
+
+/// Typed key for `CONFIG_COLOR_BASE`.
+pub const KEY_COLOR_BASE: ConfigKey<Color> = ConfigKey::builtin(CONFIG_COLOR_BASE);
+
+/// Typed key for `CONFIG_COLOR_HOVER`.
+pub const KEY_COLOR_HOVER: ConfigKey<Color> = ConfigKey::builtin(CONFIG_COLOR_HOVER);
+
+/// Typed key for `CONFIG_COLOR_BORDER`.
+pub const KEY_COLOR_BORDER: ConfigKey<Color> = ConfigKey::builtin(CONFIG_COLOR_BORDER);
+
+/// Typed key for `CONFIG_COLOR_TEXT`.
+pub const KEY_COLOR_TEXT: ConfigKey<Color> = ConfigKey::builtin(CONFIG_COLOR_TEXT);
+
+/// Typed key for `CONFIG_COLOR_SELECTED`.
+pub const KEY_COLOR_SELECTED: ConfigKey<Color> = ConfigKey::builtin(CONFIG_COLOR_SELECTED);
+
+/// Typed key for `CONFIG_COLOR_SECONDARY`.
+pub const KEY_COLOR_SECONDARY: ConfigKey<Color> = ConfigKey::builtin(CONFIG_COLOR_SECONDARY);
+
+/// Typed key for `CONFIG_ORIGIN`.
+pub const KEY_ORIGIN: ConfigKey<Points> = ConfigKey::builtin(CONFIG_ORIGIN);
+
+/// Typed key for `CONFIG_SIZE`.
+pub const KEY_SIZE: ConfigKey<Size> = ConfigKey::builtin(CONFIG_SIZE);
+
+/// Typed key for `CONFIG_BORDER_WIDTH`.
+pub const KEY_BORDER_WIDTH: ConfigKey<i32> = ConfigKey::builtin(CONFIG_BORDER_WIDTH);
+
+/// Typed key for `CONFIG_TEXT`.
+pub const KEY_TEXT: ConfigKey<String> = ConfigKey::builtin(CONFIG_TEXT);
+
+/// Typed key for `CONFIG_PROGRESS`.
+pub const KEY_PROGRESS: ConfigKey<i32> = ConfigKey::builtin(CONFIG_PROGRESS);
+
+/// Typed key for `CONFIG_IMAGE_POSITION`.
+pub const KEY_IMAGE_POSITION: ConfigKey<CompassPosition> =
+    ConfigKey::builtin(CONFIG_IMAGE_POSITION);
+
+/// Typed key for `CONFIG_FONT_SIZE`.
+pub const KEY_FONT_SIZE: ConfigKey<i32> = ConfigKey::builtin(CONFIG_FONT_SIZE);
+
+/// Typed key for `CONFIG_SELECTED_STATE`.
+pub const KEY_SELECTED_STATE: ConfigKey<bool> = ConfigKey::builtin(CONFIG_SELECTED_STATE);
+
+/// Typed key for `CONFIG_FONT`.
+pub const KEY_FONT: ConfigKey<FontRef> = ConfigKey::builtin(CONFIG_FONT);
+
 /// This enum is used by the `ImageWidget`, which controls the positioning of the image being
 /// rendered within the bounds of the `Widget`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum CompassPosition {
     /// Upper left-hand corner of the bounds.
     NW,
@@ -105,9 +165,100 @@ pub enum CompassPosition {
     SE,
 }
 
+/// This is a set of distances from each edge of a `Widget`, which is used to store padding and
+/// margins.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Insets {
+    /// Distance from the top edge.
+    pub top: i32,
+
+    /// Distance from the right edge.
+    pub right: i32,
+
+    /// Distance from the bottom edge.
+    pub bottom: i32,
+
+    /// Distance from the left edge.
+    pub left: i32,
+}
+
+/// This is the implementation of the `Insets`.
+impl Insets {
+    /// Creates new `Insets`, in the same order as CSS: `top`, `right`, `bottom`, `left`.
+    pub fn new(top: i32, right: i32, bottom: i32, left: i32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Creates new `Insets` with the same distance from each edge.
+    pub fn uniform(inset: i32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+
+    /// Returns the area that remains of `rect` once these insets are removed from it.  The area is
+    /// at least `1x1`, as a `Rect` cannot be empty.
+    pub fn apply(&self, rect: Rect) -> Rect {
+        Rect::new(
+            rect.x() + self.left,
+            rect.y() + self.top,
+            (rect.width() as i32 - self.left - self.right).max(1) as u32,
+            (rect.height() as i32 - self.top - self.bottom).max(1) as u32,
+        )
+    }
+}
+
+/// This is a reference to a font in the `ResourceCache`, which is loaded with `load_font` when it
+/// is drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FontRef {
+    /// The name of a registered `FontFamily`, or the path of a font file.
+    pub name: String,
+
+    /// The size of the font in points.
+    pub size: u16,
+
+    /// The style of the font.
+    pub style: FontStyle,
+}
+
+/// This is the implementation of the `FontRef`.
+impl FontRef {
+    /// Creates a new reference to the font `name`, in the point `size` and `style` given.
+    pub fn new(name: String, size: u16, style: FontStyle) -> Self {
+        Self { name, size, style }
+    }
+}
+
+/// This is the default `FontRef`, which refers to `DEFAULT_FONT` at 14 points.
+impl Default for FontRef {
+    fn default() -> Self {
+        Self::new(String::from(DEFAULT_FONT), 14, FontStyle::NORMAL)
+    }
+}
+
+/// This is a reference to an image in the `ResourceCache`, which is loaded with `load_image` when
+/// it is drawn.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImageRef {
+    /// The path of the image.
+    pub path: String,
+}
+
+/// This is the implementation of the `ImageRef`.
+impl ImageRef {
+    /// Creates a new reference to the image at `path`.
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
 /// Configuration object type - allows configurations to be set using `Piston`, `Pushrod`, or
 /// native types.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Config {
     /// This stores a `Points` type.
     Points(Points),
@@ -129,15 +280,220 @@ pub enum Config {
 
     /// This stores a `ComapssPosition`.
     CompassPosition(CompassPosition),
+
+    /// This stores a fractional value in the form of an `f64` value.
+    Float(f64),
+
+    /// This stores a `Rect`.
+    Rect(Rect),
+
+    /// This stores `Insets`, such as padding or margins.
+    Insets(Insets),
+
+    /// This stores a list of `String`s.
+    StringList(Vec<String>),
+
+    /// This stores a reference to a font.
+    Font(FontRef),
+
+    /// This stores a reference to an image.
+    Image(ImageRef),
+}
+
+/// This is the implementation of the `Config`.
+impl Config {
+    /// Returns the name of the type of value stored in this `Config`, which is used to report
+    /// mismatched configuration values.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Config::Points(_) => "Points",
+            Config::Size(_) => "Size",
+            Config::Color(_) => "Color",
+            Config::Numeric(_) => "Numeric",
+            Config::Text(_) => "Text",
+            Config::Toggle(_) => "Toggle",
+            Config::CompassPosition(_) => "CompassPosition",
+            Config::Float(_) => "Float",
+            Config::Rect(_) => "Rect",
+            Config::Insets(_) => "Insets",
+            Config::StringList(_) => "StringList",
+            Config::Font(_) => "Font",
+            Config::Image(_) => "Image",
+        }
+    }
+}
+
+/// This identifies a configuration value in a `WidgetConfig`.  The values used by the built-in
+/// `Widget`s are identified by the `CONFIG_*` constants, and all other values by a namespace and a
+/// name, so that `Widget`s from different crates do not collide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigKeyId {
+    /// A built-in key, such as `CONFIG_COLOR_BASE`.
+    Builtin(u8),
+
+    /// A key declared with `ConfigKey::new`, identified by its namespace and name.
+    Named(&'static str, &'static str),
+}
+
+/// This is the `Display` implementation of the `ConfigKeyId`.
+impl fmt::Display for ConfigKeyId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigKeyId::Builtin(key) => write!(f, "pushrod::{}", key),
+            ConfigKeyId::Named(namespace, name) => write!(f, "{}::{}", namespace, name),
+        }
+    }
+}
+
+/// This is a configuration key whose value is of type `T`.  Setting and retrieving values through
+/// a `ConfigKey` is checked against that type, rather than relying on the caller to use the
+/// setter and getter that match the value.
+///
+/// Custom `Widget`s declare their own keys as constants with `ConfigKey::new`, using a namespace
+/// that is unique to their crate, ie. the crate name:
+///
+/// ```ignore
+/// pub const CONFIG_DIAL_ANGLE: ConfigKey<i32> = ConfigKey::new("my_crate", "dial_angle");
+/// ```
+///
+/// Typed keys for the built-in values are available as the `KEY_*` constants.
+pub struct ConfigKey<T> {
+    id: ConfigKeyId,
+    _value: PhantomData<fn() -> T>,
+}
+
+/// This is the implementation of the `ConfigKey`.
+impl<T> ConfigKey<T> {
+    /// Declares a key `name` in the `namespace` given.
+    pub const fn new(namespace: &'static str, name: &'static str) -> Self {
+        Self {
+            id: ConfigKeyId::Named(namespace, name),
+            _value: PhantomData,
+        }
+    }
+
+    /// Declares a typed key for one of the built-in `CONFIG_*` keys.
+    pub const fn builtin(key: u8) -> Self {
+        Self {
+            id: ConfigKeyId::Builtin(key),
+            _value: PhantomData,
+        }
+    }
+
+    /// Returns the `ConfigKeyId` that identifies this key.
+    pub fn id(&self) -> ConfigKeyId {
+        self.id
+    }
 }
 
+impl<T> Clone for ConfigKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ConfigKey<T> {}
+
+impl<T> fmt::Debug for ConfigKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ConfigKey({})", self.id)
+    }
+}
+
+/// This trait is implemented by the types that can be stored in a `WidgetConfig` through a
+/// `ConfigKey`, converting them to and from a `Config`.
+pub trait ConfigValue: Sized {
+    /// The name of this type, as reported in a `ConfigError::Mismatch`.
+    fn type_name() -> &'static str;
+
+    /// Converts the value to a `Config`.
+    fn into_config(self) -> Config;
+
+    /// Converts a `Config` to a value of this type, returning `None` if it stores another type.
+    fn from_config(config: &Config) -> Option<Self>;
+}
+
+macro_rules! config_value {
+    ($value_type:ty, $variant:ident) => {
+        impl ConfigValue for $value_type {
+            fn type_name() -> &'static str {
+                stringify!($variant)
+            }
+
+            fn into_config(self) -> Config {
+                Config::$variant(self)
+            }
+
+            fn from_config(config: &Config) -> Option<Self> {
+                match config {
+                    Config::$variant(value) => Some(value.clone()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+config_value!(Points, Points);
+config_value!(Size, Size);
+config_value!(Color, Color);
+config_value!(i32, Numeric);
+config_value!(String, Text);
+config_value!(bool, Toggle);
+config_value!(CompassPosition, CompassPosition);
+config_value!(f64, Float);
+config_value!(Rect, Rect);
+config_value!(Insets, Insets);
+config_value!(Vec<String>, StringList);
+config_value!(FontRef, Font);
+config_value!(ImageRef, Image);
+
+/// This is an error that is returned when a configuration value is retrieved through a
+/// `ConfigKey`, and it is not set, or is not of the type of the key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    /// No value is set for the key.
+    Missing(ConfigKeyId),
+
+    /// The value that is set for the key is not of the type of the key.  Contains the key, the
+    /// type of the key, and the type of the value that is set.
+    Mismatch(ConfigKeyId, &'static str, &'static str),
+}
+
+/// This is the `Display` implementation of the `ConfigError`.
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Missing(key) => write!(f, "No value is set for {}", key),
+            ConfigError::Mismatch(key, expected, found) => write!(
+                f,
+                "Value of {} is a {}, but a {} was expected",
+                key, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
 /// This is the store for the `WidgetConfig`, which each `Widget` object needs.  This stores
 /// information about the `Widget`.  It currently contains the point of origin, size, a `HashMap` of
 /// different `Color`s, a border width, and an invalidation flag.
+///
+/// Values are set with `set` and retrieved with `try_get` through a `ConfigKey`, which reports a
+/// value that is not set, or is of another type, as a `ConfigError`.  Keys can be declared by
+/// custom `Widget`s with `ConfigKey::new`, and the built-in keys are available as `KEY_*` constants.
+/// The setters for built-in `u8` keys, such as `set_color`, can still be used.  The matching
+/// getters, such as `get_color`, are deprecated, as they return a default value instead of
+/// reporting a missing or mistyped value.
 pub struct WidgetConfig {
     /// The `HashMap` store for configuration objects.
     pub config: HashMap<u8, Config>,
 
+    /// The store for configuration objects declared with `ConfigKey::new`, keyed by namespace and
+    /// name.
+    named_config: HashMap<(&'static str, &'static str), Config>,
+
     /// `Widget`'s hidden flag - any children that refer to this object as a `parent_id` will not
     /// be drawn, and their events will not be received.
     hidden: bool,
@@ -147,6 +503,13 @@ pub struct WidgetConfig {
 
     /// `Widget`'s redraw flag.  Set `true` if the object needs to be redrawn, `false` otherwise.
     invalidated: bool,
+
+    /// Flag indicating whether or not the `WidgetCache` keeps a rendered copy of this `Widget`.
+    render_cached: bool,
+
+    /// Flag that is raised when the origin or size of the `Widget` changes, which is shared with
+    /// the `WidgetCache` that stores this `Widget`.
+    geometry_listener: Option<Rc<Cell<bool>>>,
 }
 
 /// This is the implementation of the `WidgetConfig`.
@@ -164,9 +527,12 @@ impl WidgetConfig {
             .iter()
             .cloned()
             .collect(),
+            named_config: HashMap::new(),
             hidden: false,
             enabled: true,
             invalidated: true,
+            render_cached: false,
+            geometry_listener: None,
         }
     }
 
@@ -174,14 +540,14 @@ impl WidgetConfig {
     /// Returns `i32` containing the modified X coordinate.  This is a convenience method for the
     /// `Widget` to draw based on a 0x0 point of origin.
     pub fn to_x(&self, x: i32) -> i32 {
-        self.get_point(CONFIG_ORIGIN)[0] + x
+        self.try_get(KEY_ORIGIN).unwrap_or_default()[0] + x
     }
 
     /// Converts a Y point to the physical Y point on the `Canvas` plus the point of origin.
     /// Returns `i32` containing the modified Y coordinate.  This is a convenience method for the
     /// `Widget` to draw based on a 0x0 point of origin.
     pub fn to_y(&self, y: i32) -> i32 {
-        self.get_point(CONFIG_ORIGIN)[1] + y
+        self.try_get(KEY_ORIGIN).unwrap_or_default()[1] + y
     }
 
     /// Sets the invalidation state of the `Widget`, telling the `Engine` that the `Widget`
@@ -235,14 +601,46 @@ impl WidgetConfig {
         self.hidden
     }
 
+    /// Opts the `Widget` in (`true`) or out (`false`) of render caching.  A render cached `Widget`
+    /// is drawn into an offscreen `Surface` only when it is invalidated or resized, and the
+    /// `WidgetCache` copies that `Surface` to the screen when the `Widget` needs to be repainted
+    /// for any other reason, such as a `Widget` below it being redrawn.  This benefits `Widget`s
+    /// that are expensive to draw, such as text and images.  Only the drawing of the `Widget`
+    /// itself is cached, not that of its children.  Render caching is off by default, as it only
+    /// pays off for `Widget`s that are repainted more often than they are invalidated.
+    pub fn set_render_cached(&mut self, flag: bool) {
+        self.render_cached = flag;
+        self.invalidated = true;
+    }
+
+    /// Indicates whether or not the `Widget` is render cached.
+    pub fn is_render_cached(&self) -> bool {
+        self.render_cached
+    }
+
+    /// Assigns the flag that is raised whenever `CONFIG_ORIGIN` or `CONFIG_SIZE` is changed through
+    /// `set_point` or `set_size`.  The `WidgetCache` uses this to keep its spatial index up to
+    /// date, so changing these values by modifying `config` directly is not detected.
+    pub fn set_geometry_listener(&mut self, listener: Rc<Cell<bool>>) {
+        self.geometry_listener = Some(listener);
+    }
+
     /// Sets a point for a configuration key.
     pub fn set_point(&mut self, config: u8, x: i32, y: i32) {
         self.config.insert(config, Config::Points(vec![x, y]));
+
+        if config == CONFIG_ORIGIN {
+            self.geometry_changed();
+        }
     }
 
     /// Sets a size for a configuration key.
     pub fn set_size(&mut self, config: u8, w: u32, h: u32) {
         self.config.insert(config, Config::Size(vec![w, h]));
+
+        if config == CONFIG_SIZE {
+            self.geometry_changed();
+        }
     }
 
     /// Sets a color for a configuration key.
@@ -270,59 +668,226 @@ impl WidgetConfig {
         self.config.insert(config, Config::CompassPosition(value));
     }
 
+    /// Sets a fractional value for a configuration key.
+    pub fn set_float(&mut self, config: u8, value: f64) {
+        self.config.insert(config, Config::Float(value));
+    }
+
+    /// Sets a `Rect` for a configuration key.
+    pub fn set_rect(&mut self, config: u8, rect: Rect) {
+        self.config.insert(config, Config::Rect(rect));
+    }
+
+    /// Sets `Insets` for a configuration key.
+    pub fn set_insets(&mut self, config: u8, insets: Insets) {
+        self.config.insert(config, Config::Insets(insets));
+    }
+
+    /// Sets a list of `String`s for a configuration key.
+    pub fn set_string_list(&mut self, config: u8, list: Vec<String>) {
+        self.config.insert(config, Config::StringList(list));
+    }
+
+    /// Sets a font reference for a configuration key.
+    pub fn set_font(&mut self, config: u8, font: FontRef) {
+        self.config.insert(config, Config::Font(font));
+    }
+
+    /// Sets an image reference for a configuration key.
+    pub fn set_image(&mut self, config: u8, image: ImageRef) {
+        self.config.insert(config, Config::Image(image));
+    }
+
+    /// Sets the configuration value identified by `key`.  This is the untyped form of `set`, which
+    /// can be used when the type of the value is only known at runtime.
+    pub fn set_config(&mut self, key: ConfigKeyId, value: Config) {
+        match key {
+            ConfigKeyId::Builtin(k) => {
+                self.config.insert(k, value);
+
+                if k == CONFIG_ORIGIN || k == CONFIG_SIZE {
+                    self.geometry_changed();
+                }
+            }
+
+            ConfigKeyId::Named(namespace, name) => {
+                self.named_config.insert((namespace, name), value);
+            }
+        }
+    }
+
+    /// Retrieves the configuration value identified by `key`, if it is set.
+    pub fn get_config(&self, key: ConfigKeyId) -> Option<&Config> {
+        match key {
+            ConfigKeyId::Builtin(k) => self.config.get(&k),
+            ConfigKeyId::Named(namespace, name) => self.named_config.get(&(namespace, name)),
+        }
+    }
+
+    /// Removes the configuration value identified by `key`, returning it if it was set.
+    pub fn remove_config(&mut self, key: ConfigKeyId) -> Option<Config> {
+        match key {
+            ConfigKeyId::Builtin(k) => self.config.remove(&k),
+            ConfigKeyId::Named(namespace, name) => self.named_config.remove(&(namespace, name)),
+        }
+    }
+
+    /// Sets the `value` of a typed configuration `key`.
+    pub fn set<T: ConfigValue>(&mut self, key: ConfigKey<T>, value: T) {
+        self.set_config(key.id(), value.into_config());
+    }
+
+    /// Retrieves the value of a typed configuration `key`.  Returns `ConfigError::Missing` if the
+    /// value is not set, or `ConfigError::Mismatch` if it was set to a value of another type
+    /// through the untyped setters.
+    pub fn try_get<T: ConfigValue>(&self, key: ConfigKey<T>) -> Result<T, ConfigError> {
+        match self.get_config(key.id()) {
+            Some(config) => T::from_config(config)
+                .ok_or_else(|| ConfigError::Mismatch(key.id(), T::type_name(), config.type_name())),
+            None => Err(ConfigError::Missing(key.id())),
+        }
+    }
+
     /// Retrieves a `Points` for a configuration key.  Returns `Points::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_point(&self, k: u8) -> Points {
-        match self.config.get(&k) {
-            Some(Config::Points(point)) => point.clone(),
-            _ => Points::default(),
-        }
+        self.get_or_default(k, Points::default())
     }
 
     /// Retrieves a `Size` for a configuration key.  Returns a `Size::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_size(&self, k: u8) -> Size {
-        match self.config.get(&k) {
-            Some(Config::Size(size)) => size.clone(),
-            _ => Size::default(),
-        }
+        self.get_or_default(k, Size::default())
     }
 
     /// Retrieves a `Color` for a configuration key.  Returns white if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_color(&self, k: u8) -> Color {
-        match self.config.get(&k) {
-            Some(Config::Color(color)) => *color,
-            _ => Color::RGB(255, 255, 255),
-        }
+        self.get_or_default(k, Color::RGB(255, 255, 255))
     }
 
     /// Retrieves a numeric value for a configuration key.  Returns 0 if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_numeric(&self, k: u8) -> i32 {
-        match self.config.get(&k) {
-            Some(Config::Numeric(numeric)) => *numeric,
-            _ => 0,
-        }
+        self.get_or_default(k, 0)
     }
 
     /// Retrieves text for a configuration key.  Returns a blank string if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_text(&self, k: u8) -> String {
-        match self.config.get(&k) {
-            Some(Config::Text(text)) => text.clone(),
-            _ => String::from(""),
-        }
+        self.get_or_default(k, String::from(""))
     }
 
     /// Retrieves a boolean toggle for a configuration key.  Returns `false` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_toggle(&self, k: u8) -> bool {
-        match self.config.get(&k) {
-            Some(Config::Toggle(toggle)) => *toggle,
-            _ => false,
-        }
+        self.get_or_default(k, false)
     }
 
-    /// Retrieves a `CompassPosition` toggle for a configuration key.  Returns `CompassPosition::W` if not set.
+    /// Retrieves a `CompassPosition` toggle for a configuration key.  Returns `CompassPosition::W`
+    /// if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
     pub fn get_compass(&self, k: u8) -> CompassPosition {
-        match self.config.get(&k) {
-            Some(Config::CompassPosition(position)) => position.clone(),
-            _ => CompassPosition::W,
+        self.get_or_default(k, CompassPosition::W)
+    }
+
+    /// Retrieves a fractional value for a configuration key.  Returns 0.0 if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    pub fn get_float(&self, k: u8) -> f64 {
+        self.get_or_default(k, 0.0)
+    }
+
+    /// Retrieves a `Rect` for a configuration key.  Returns a `1x1` `Rect` at `0x0` if not set, as
+    /// a `Rect` cannot be empty.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    pub fn get_rect(&self, k: u8) -> Rect {
+        self.get_or_default(k, Rect::new(0, 0, 1, 1))
+    }
+
+    /// Retrieves `Insets` for a configuration key.  Returns `Insets::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    pub fn get_insets(&self, k: u8) -> Insets {
+        self.get_or_default(k, Insets::default())
+    }
+
+    /// Retrieves a list of `String`s for a configuration key.  Returns an empty list if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    pub fn get_string_list(&self, k: u8) -> Vec<String> {
+        self.get_or_default(k, Vec::new())
+    }
+
+    /// Retrieves a font reference for a configuration key.  Returns `FontRef::default` if not set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    pub fn get_font(&self, k: u8) -> FontRef {
+        self.get_or_default(k, FontRef::default())
+    }
+
+    /// Retrieves an image reference for a configuration key.  Returns `ImageRef::default` if not
+    /// set.
+    #[deprecated(
+        since = "0.4.13",
+        note = "Use `try_get` with a `ConfigKey`, which reports missing and mistyped values"
+    )]
+    pub fn get_image(&self, k: u8) -> ImageRef {
+        self.get_or_default(k, ImageRef::default())
+    }
+
+    /// Retrieves the value of the built-in key `k` for the typed getters, returning `default` if
+    /// it is not set, or is of another type.  A value of another type is logged as a warning, as
+    /// it is set by a `Widget` that uses the key for something else.
+    fn get_or_default<T: ConfigValue>(&self, k: u8, default: T) -> T {
+        match self.try_get(ConfigKey::builtin(k)) {
+            Ok(value) => value,
+            Err(e @ ConfigError::Mismatch(..)) => {
+                warn!(target: "pushrod::config", "{}; using the default value", e);
+                default
+            }
+            Err(e) => {
+                trace!(target: "pushrod::config", "{}; using the default value", e);
+                default
+            }
+        }
+    }
+
+    fn geometry_changed(&self) {
+        if let Some(listener) = &self.geometry_listener {
+            listener.set(true);
         }
     }
 }
@@ -19,8 +19,11 @@ use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
 use crate::render::Points;
 
-use sdl2::render::Canvas;
-use sdl2::video::Window;
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::{
+    CHECKBOX_SELECTED_IMAGE_PATH, CHECKBOX_UNSELECTED_IMAGE_PATH, DEFAULT_FONT,
+};
 
 use crate::render::widget_config::CompassPosition::Center;
 use crate::widgets::image_widget::ImageWidget;
@@ -63,7 +66,7 @@ impl CheckboxWidget {
         selected: bool,
     ) -> Self {
         let mut text_widget = TextWidget::new(
-            String::from("assets/OpenSans-Regular.ttf"),
+            String::from(DEFAULT_FONT),
             sdl2::ttf::FontStyle::NORMAL,
             font_size,
             TextJustify::Left,
@@ -82,7 +85,7 @@ impl CheckboxWidget {
 
         let mut config = WidgetConfig::new(x, y, w, h);
         let mut unchecked_widget = ImageWidget::new(
-            String::from("assets/checkbox_unselected.png"),
+            String::from(CHECKBOX_UNSELECTED_IMAGE_PATH),
             x + 2,
             y + 2,
             h - 4,
@@ -90,7 +93,7 @@ impl CheckboxWidget {
             true,
         );
         let mut checked_widget = ImageWidget::new(
-            String::from("assets/checkbox_selected.png"),
+            String::from(CHECKBOX_SELECTED_IMAGE_PATH),
             x + 2,
             y + 2,
             h - 4,
@@ -137,33 +140,40 @@ impl CheckboxWidget {
 
 /// This is the `Widget` implementation of the `ToggleButtonWidget`.
 impl Widget for CheckboxWidget {
-    /// Draws the `CheckboxWidget` contents.
-    fn draw(&mut self, c: &mut Canvas<Window>) {
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the `CheckboxWidget` contents, returning an error if its image or text could not be
+    /// drawn.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
         // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
         // Invalidation is controlled by the top level widget (this box).
         if self.active {
             if self.in_bounds {
                 if self.selected {
-                    self.unchecked_widget.draw(c);
+                    self.unchecked_widget.try_draw(c)?;
                 } else {
-                    self.checked_widget.draw(c);
+                    self.checked_widget.try_draw(c)?;
                 }
             } else {
                 if self.selected {
-                    self.checked_widget.draw(c);
+                    self.checked_widget.try_draw(c)?;
                 } else {
-                    self.unchecked_widget.draw(c);
+                    self.unchecked_widget.try_draw(c)?;
                 }
             }
         } else {
             if self.selected {
-                self.checked_widget.draw(c);
+                self.checked_widget.try_draw(c)?;
             } else {
-                self.unchecked_widget.draw(c);
+                self.unchecked_widget.try_draw(c)?;
             }
         }
 
-        self.text_widget.draw(c);
+        self.text_widget.try_draw(c)?;
+
+        Ok(())
     }
 
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.
@@ -207,6 +217,22 @@ impl Widget for CheckboxWidget {
         self.button_clicked_callback(_widgets, _button, _clicks, _state);
     }
 
+    /// Updates the selected state when `CONFIG_SELECTED_STATE` is changed, and passes a new
+    /// `CONFIG_FONT` on to the text of the `Widget`.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_SELECTED_STATE, Config::Toggle(selected)) => {
+                self.selected = selected;
+                self.get_config().set_invalidate(true);
+            }
+            (CONFIG_FONT, Config::Font(font)) => {
+                self.text_widget.set_font(CONFIG_FONT, font);
+                self.get_config().set_invalidate(true);
+            }
+            _ => (),
+        }
+    }
+
     default_widget_properties!();
     default_widget_callbacks!();
 }
@@ -17,17 +17,19 @@ use crate::render::callbacks::CallbackRegistry;
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::{
-    WidgetConfig, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT,
-    CONFIG_IMAGE_POSITION,
+    Config, WidgetConfig, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+    CONFIG_COLOR_TEXT, CONFIG_FONT, CONFIG_IMAGE_POSITION,
 };
 use crate::render::Points;
 
-use sdl2::render::Canvas;
-use sdl2::video::Window;
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::DEFAULT_FONT;
 
 use crate::render::widget_config::CompassPosition::Center;
 use crate::widgets::image_widget::ImageWidget;
 use crate::widgets::text_widget::{TextJustify, TextWidget};
+use log::trace;
 use sdl2::pixels::Color;
 use std::collections::HashMap;
 
@@ -60,7 +62,7 @@ impl ImageButtonWidget {
     ) -> Self {
         let mut base_widget = BaseWidget::new(x, y, w, h);
         let mut text_widget = TextWidget::new(
-            String::from("assets/OpenSans-Regular.ttf"),
+            String::from(DEFAULT_FONT),
             sdl2::ttf::FontStyle::NORMAL,
             font_size,
             TextJustify::Left,
@@ -128,12 +130,19 @@ impl ImageButtonWidget {
 
 /// This is the `Widget` implementation of the `ImageButtonWidget`.
 impl Widget for ImageButtonWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>) {
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the `ImageButtonWidget` contents, returning an error if its text or image could not be drawn.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
         // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
         // Invalidation is controlled by the top level widget (this box).
-        self.base_widget.draw(c);
-        self.text_widget.draw(c);
-        self.image_widget.draw(c);
+        self.base_widget.try_draw(c)?;
+        self.text_widget.try_draw(c)?;
+        self.image_widget.try_draw(c)?;
+
+        Ok(())
     }
 
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.  This function
@@ -185,7 +194,7 @@ impl Widget for ImageButtonWidget {
 
                 if self.in_bounds && had_bounds {
                     // Callback here
-                    eprintln!("Call callback here: clicks={}", _clicks);
+                    trace!(target: "pushrod::events", "Button clicked: clicks={}", _clicks);
                     self.call_click_callback(_widgets);
                 }
             }
@@ -194,6 +203,14 @@ impl Widget for ImageButtonWidget {
         self.button_clicked_callback(_widgets, _button, _clicks, _state);
     }
 
+    /// Passes a new `CONFIG_FONT` on to the text of the button.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        if let (CONFIG_FONT, Config::Font(font)) = (_k, _v) {
+            self.text_widget.set_font(CONFIG_FONT, font);
+            self.get_config().set_invalidate(true);
+        }
+    }
+
     default_widget_properties!();
     default_widget_callbacks!();
 }
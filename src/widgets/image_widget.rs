@@ -17,17 +17,19 @@ use crate::render::callbacks::CallbackRegistry;
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::{
-    CompassPosition, Config, WidgetConfig, CONFIG_COLOR_BASE, CONFIG_IMAGE_POSITION, CONFIG_SIZE,
+    CompassPosition, Config, WidgetConfig, CONFIG_IMAGE_POSITION, KEY_COLOR_BASE,
+    KEY_IMAGE_POSITION, KEY_SIZE,
 };
 use crate::render::Points;
 
-use sdl2::image::LoadTexture;
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::load_image;
+
+use sdl2::pixels::Color;
 use sdl2::rect::Rect;
-use sdl2::render::{Canvas, TextureQuery};
-use sdl2::video::Window;
 
 use std::collections::HashMap;
-use std::path::Path;
 
 /// This is the storage object for the `TextWidget`.  It stores the config, properties, callback registry,
 /// the font name, style, size, justification, and text message.
@@ -49,7 +51,8 @@ impl ImageWidget {
     /// If `scaled` is set to `true`, the image will be scaled within the `Widget` bounds, and the
     /// `ImagePosition` will be ignored.  Likewise, if set to `false`, the image will be displayed for
     /// the size of the image, and will be placed in the bounds of the `Widget` based on the position
-    /// specified in the `ImagePosition`.
+    /// specified in the `ImagePosition`.  Use `set_render_cached` on the `WidgetConfig` to have the
+    /// image only drawn again when the `Widget` is invalidated.
     pub fn new(image_name: String, x: i32, y: i32, w: u32, h: u32, scaled: bool) -> Self {
         Self {
             config: WidgetConfig::new(x, y, w, h),
@@ -61,24 +64,33 @@ impl ImageWidget {
     }
 }
 
-/// This is the `Widget` implementation of the `ImageWidget`.  Image is rendered onto a 3D texture, then
-/// copied to the canvas after rendering.
+/// This is the `Widget` implementation of the `ImageWidget`.  Image is loaded into a `Surface`, then
+/// copied to the canvas after loading.
 impl Widget for ImageWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>) {
-        let base_color = self.get_color(CONFIG_COLOR_BASE);
-
-        c.set_draw_color(base_color);
-        c.fill_rect(self.get_drawing_area()).unwrap();
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
 
-        let texture_creator = c.texture_creator();
-        let texture = texture_creator
-            .load_texture(Path::new(&self.image_name))
-            .unwrap();
-        let widget_w = self.get_size(CONFIG_SIZE)[0] as i32;
-        let widget_h = self.get_size(CONFIG_SIZE)[1] as i32;
-        let TextureQuery { width, height, .. } = texture.query();
+    /// Draws the image, returning an error if it could not be loaded.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
+        let base_color = self
+            .try_get(KEY_COLOR_BASE)
+            .unwrap_or(Color::RGB(255, 255, 255));
 
-        let texture_x = match self.get_compass(CONFIG_IMAGE_POSITION) {
+        c.set_draw_color(base_color);
+        c.fill_rect(self.get_drawing_area())
+            .map_err(PushrodError::Render)?;
+
+        let image = load_image(c, &self.image_name)?;
+        let widget_w = self.try_get(KEY_SIZE).unwrap_or_default()[0] as i32;
+        let widget_h = self.try_get(KEY_SIZE).unwrap_or_default()[1] as i32;
+        let width = image.width();
+        let height = image.height();
+
+        let texture_x = match self
+            .try_get(KEY_IMAGE_POSITION)
+            .unwrap_or(CompassPosition::W)
+        {
             CompassPosition::NW | CompassPosition::W | CompassPosition::SW => {
                 self.get_config().to_x(0)
             }
@@ -92,7 +104,10 @@ impl Widget for ImageWidget {
             }
         };
 
-        let texture_y = match self.get_compass(CONFIG_IMAGE_POSITION) {
+        let texture_y = match self
+            .try_get(KEY_IMAGE_POSITION)
+            .unwrap_or(CompassPosition::W)
+        {
             CompassPosition::NW | CompassPosition::N | CompassPosition::NE => {
                 self.get_config().to_y(0)
             }
@@ -107,24 +122,26 @@ impl Widget for ImageWidget {
         };
 
         if !self.scaled {
-            c.copy(
-                &texture,
+            c.copy_image(
+                &self.image_name,
+                &image,
                 None,
                 Rect::new(texture_x, texture_y, width, height),
             )
-            .unwrap();
+            .map_err(PushrodError::Render)
         } else {
-            c.copy(
-                &texture,
+            c.copy_image(
+                &self.image_name,
+                &image,
                 None,
                 Rect::new(
-                    self.get_config().to_x(0),
-                    self.get_config().to_y(0),
+                    self.config.to_x(0),
+                    self.config.to_y(0),
                     widget_w as u32,
                     widget_h as u32,
                 ),
             )
-            .unwrap();
+            .map_err(PushrodError::Render)
         }
     }
 
@@ -44,3 +44,8 @@ pub mod image_button_widget;
 /// box with a black/white color on select.  Rather, it enables/disables a checkbox to indicate a
 /// selected option.
 pub mod checkbox_widget;
+
+/// This is a `TextBoxWidget`, which is a single-line text entry field.  It supports cursor movement,
+/// selection, and text input through input methods.  It triggers `on_text_changed` callbacks when
+/// the text is modified, and `on_submit` callbacks when `Enter` is pressed.
+pub mod text_box_widget;
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use crate::render::callbacks::CallbackRegistry;
+use crate::render::error::PushrodError;
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
@@ -21,9 +22,8 @@ use crate::render::Points;
 
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
-use sdl2::video::Window;
 
-use sdl2::render::Canvas;
+use crate::render::canvas::RenderCanvas;
 use std::collections::HashMap;
 
 /// This is the storage object for the `ProgressWidget`.  It stores the config, properties, callback registry,
@@ -66,21 +66,30 @@ impl ProgressWidget {
 /// This is the `Widget` implementation of the `ProgressWidget`.  It contains a `BaseWidget` within
 /// its bounds to draw the base background, then draws the progress fill over the top.
 impl Widget for ProgressWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>) {
-        self.base_widget.draw(c);
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the base and the progress fill, returning an error if the canvas could not be drawn
+    /// to.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
+        self.base_widget.try_draw(c)?;
 
-        let base_color = self.get_color(CONFIG_COLOR_SECONDARY);
-        let progress = (f64::from(self.get_size(CONFIG_SIZE)[0])
-            * (f64::from(self.get_numeric(CONFIG_PROGRESS)) / 100.0)) as u32;
+        let base_color = self
+            .try_get(KEY_COLOR_SECONDARY)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let progress = (f64::from(self.try_get(KEY_SIZE).unwrap_or_default()[0])
+            * (f64::from(self.try_get(KEY_PROGRESS).unwrap_or_default()) / 100.0))
+            as u32;
 
         c.set_draw_color(base_color);
         c.fill_rect(Rect::new(
             self.config.to_x(1),
             self.config.to_y(1),
             progress,
-            self.get_size(CONFIG_SIZE)[1] - 2,
+            self.try_get(KEY_SIZE).unwrap_or_default()[1] - 2,
         ))
-        .unwrap();
+        .map_err(PushrodError::Render)
     }
 
     /// Responds to a screen redraw only if the `CONFIG_PROGRESS` key was changed.
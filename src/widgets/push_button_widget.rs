@@ -17,14 +17,17 @@ use crate::render::callbacks::CallbackRegistry;
 use crate::render::widget::*;
 use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::{
-    WidgetConfig, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER, CONFIG_COLOR_TEXT,
+    Config, WidgetConfig, CONFIG_BORDER_WIDTH, CONFIG_COLOR_BASE, CONFIG_COLOR_BORDER,
+    CONFIG_COLOR_TEXT, CONFIG_FONT,
 };
 use crate::render::Points;
 
-use sdl2::render::Canvas;
-use sdl2::video::Window;
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::DEFAULT_FONT;
 
 use crate::widgets::text_widget::{TextJustify, TextWidget};
+use log::trace;
 use sdl2::pixels::Color;
 use std::collections::HashMap;
 
@@ -48,7 +51,7 @@ impl PushButtonWidget {
     pub fn new(x: i32, y: i32, w: u32, h: u32, text: String, font_size: i32) -> Self {
         let mut base_widget = BaseWidget::new(x, y, w, h);
         let mut text_widget = TextWidget::new(
-            String::from("assets/OpenSans-Regular.ttf"),
+            String::from(DEFAULT_FONT),
             sdl2::ttf::FontStyle::NORMAL,
             font_size,
             TextJustify::Center,
@@ -116,11 +119,18 @@ impl PushButtonWidget {
 
 /// This is the `Widget` implementation of the `PushButtonWidget`.
 impl Widget for PushButtonWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>) {
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the `PushButtonWidget` contents, returning an error if its text could not be drawn.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
         // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
         // Invalidation is controlled by the top level widget (this box).
-        self.base_widget.draw(c);
-        self.text_widget.draw(c);
+        self.base_widget.try_draw(c)?;
+        self.text_widget.try_draw(c)?;
+
+        Ok(())
     }
 
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.  This function
@@ -172,7 +182,7 @@ impl Widget for PushButtonWidget {
 
                 if self.in_bounds && had_bounds {
                     // Callback here
-                    eprintln!("Call callback here: clicks={}", _clicks);
+                    trace!(target: "pushrod::events", "Button clicked: clicks={}", _clicks);
                     self.call_click_callback(_widgets);
                 }
             }
@@ -181,6 +191,14 @@ impl Widget for PushButtonWidget {
         self.button_clicked_callback(_widgets, _button, _clicks, _state);
     }
 
+    /// Passes a new `CONFIG_FONT` on to the text of the button.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        if let (CONFIG_FONT, Config::Font(font)) = (_k, _v) {
+            self.text_widget.set_font(CONFIG_FONT, font);
+            self.get_config().set_invalidate(true);
+        }
+    }
+
     default_widget_properties!();
     default_widget_callbacks!();
 }
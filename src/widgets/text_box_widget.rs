@@ -0,0 +1,637 @@
+// Pushrod Widget Library
+// Text Box Widget
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::render::callbacks::CallbackRegistry;
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::load_font;
+use crate::render::widget::*;
+use crate::render::widget_cache::WidgetContainer;
+use crate::render::widget_config::*;
+use crate::render::Points;
+
+use sdl2::keyboard::{Keycode, Mod};
+use sdl2::pixels::Color;
+use sdl2::rect::{Point, Rect};
+use sdl2::ttf::FontStyle;
+
+use std::collections::HashMap;
+
+/// This is the callback type that is used when an `on_text_changed` or `on_submit` callback is
+/// triggered from this `Widget`.  The `String` is the current text of the `TextBoxWidget`.
+pub type OnTextCallbackType =
+    Option<Box<dyn FnMut(&mut TextBoxWidget, &[WidgetContainer], String)>>;
+
+/// Number of pixels between the border of the `TextBoxWidget` and the text inside of it.
+const TEXT_PADDING: i32 = 3;
+
+/// This is the storage object for the `TextBoxWidget`.  It stores the config, properties, callback
+/// registry, the font name and size, the text being edited, the cursor and selection positions,
+/// the horizontal scroll offset, and any in-progress input method (IME) composition.
+pub struct TextBoxWidget {
+    config: WidgetConfig,
+    system_properties: HashMap<i32, String>,
+    callback_registry: CallbackRegistry,
+    font_name: String,
+    font_size: i32,
+    text: String,
+    cursor: usize,
+    anchor: usize,
+    scroll_x: i32,
+    composition: String,
+    composition_cursor: usize,
+    char_offsets: Vec<i32>,
+    focused: bool,
+    selecting: bool,
+    mouse_x: i32,
+    on_text_changed: OnTextCallbackType,
+    on_submit: OnTextCallbackType,
+}
+
+/// This is the implementation of the `TextBoxWidget`, a single-line text entry field.  Text is
+/// entered through SDL's text input events, so input methods for composed languages are
+/// supported, and their pre-edit text is displayed underlined at the cursor position.
+impl TextBoxWidget {
+    /// Creates a new `TextBoxWidget` given the name of the font (a font registered in the
+    /// `ResourceCache`, such as `DEFAULT_FONT`, or the path to a font file), the size in pixels of
+    /// the font, the initial `text` to display, and the `x, y, w, h` coordinates of the `Widget`.
+    /// The cursor is placed at the end of the initial text.
+    pub fn new(
+        font_name: String,
+        font_size: i32,
+        text: String,
+        x: i32,
+        y: i32,
+        w: u32,
+        h: u32,
+    ) -> Self {
+        let mut config = WidgetConfig::new(x, y, w, h);
+        let cursor = text.chars().count();
+
+        config.set_color(CONFIG_COLOR_TEXT, Color::RGB(0, 0, 0));
+        config.set_color(CONFIG_COLOR_BORDER, Color::RGB(0, 0, 0));
+        config.set_color(CONFIG_COLOR_SELECTED, Color::RGB(160, 200, 255));
+        config.set_numeric(CONFIG_BORDER_WIDTH, 1);
+        config.set_text(CONFIG_TEXT, text.clone());
+
+        Self {
+            config,
+            system_properties: HashMap::new(),
+            callback_registry: CallbackRegistry::new(),
+            font_name,
+            font_size,
+            text,
+            cursor,
+            anchor: cursor,
+            scroll_x: 0,
+            composition: String::new(),
+            composition_cursor: 0,
+            char_offsets: Vec::new(),
+            focused: false,
+            selecting: false,
+            mouse_x: 0,
+            on_text_changed: None,
+            on_submit: None,
+        }
+    }
+
+    /// Assigns the callback closure that will be used when the text is modified by the user.
+    pub fn on_text_changed<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TextBoxWidget, &[WidgetContainer], String) + 'static,
+    {
+        self.on_text_changed = Some(Box::new(callback));
+    }
+
+    /// Assigns the callback closure that will be used when the user presses the `Enter` key.
+    pub fn on_submit<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut TextBoxWidget, &[WidgetContainer], String) + 'static,
+    {
+        self.on_submit = Some(Box::new(callback));
+    }
+
+    /// Returns the current cursor position, as a character index into the text.
+    pub fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns the currently selected range of characters as a `(start, end)` pair.  If no text is
+    /// selected, `start` and `end` are both equal to the cursor position.
+    pub fn get_selection(&self) -> (usize, usize) {
+        if self.anchor < self.cursor {
+            (self.anchor, self.cursor)
+        } else {
+            (self.cursor, self.anchor)
+        }
+    }
+
+    /// Selects the characters in the range of `start` to `end`, placing the cursor at `end`.
+    /// Values past the end of the text are clamped.
+    pub fn set_selection(&mut self, start: usize, end: usize) {
+        let len = self.text.chars().count();
+
+        self.anchor = start.min(len);
+        self.cursor = end.min(len);
+        self.get_config().set_invalidate(true);
+    }
+
+    /// Internal function that triggers the `on_text_changed` callback.
+    fn call_text_changed_callback(&mut self, widgets: &[WidgetContainer]) {
+        if let Some(mut cb) = self.on_text_changed.take() {
+            cb(self, widgets, self.text.clone());
+            self.on_text_changed = Some(cb);
+        }
+    }
+
+    /// Internal function that triggers the `on_submit` callback.
+    fn call_submit_callback(&mut self, widgets: &[WidgetContainer]) {
+        if let Some(mut cb) = self.on_submit.take() {
+            cb(self, widgets, self.text.clone());
+            self.on_submit = Some(cb);
+        }
+    }
+
+    /// Converts a character index into a byte index of the text.
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| self.text.len())
+    }
+
+    fn has_selection(&self) -> bool {
+        self.anchor != self.cursor
+    }
+
+    /// Removes the selected text, placing the cursor at the start of the selection.  Returns
+    /// `true` if any text was removed.
+    fn delete_selection(&mut self) -> bool {
+        if !self.has_selection() {
+            return false;
+        }
+
+        let (start, end) = self.get_selection();
+        let byte_start = self.byte_index(start);
+        let byte_end = self.byte_index(end);
+
+        self.text.replace_range(byte_start..byte_end, "");
+        self.cursor = start;
+        self.anchor = start;
+
+        true
+    }
+
+    /// Stores the modified text in the config, redraws, and informs the `on_text_changed` callback.
+    fn text_changed(&mut self, widgets: &[WidgetContainer]) {
+        let text = self.text.clone();
+
+        self.get_config().set_text(CONFIG_TEXT, text);
+        self.get_config().set_invalidate(true);
+        self.call_text_changed_callback(widgets);
+    }
+
+    /// Moves the cursor to `position`.  If `extend` is set, the selection anchor is left in place,
+    /// extending the selection; otherwise, the selection is cleared.  Positions past the end of
+    /// the text are clamped.
+    fn move_cursor(&mut self, position: usize, extend: bool) {
+        let len = self.text.chars().count();
+
+        self.cursor = position.min(len);
+
+        if extend {
+            self.anchor = self.anchor.min(len);
+        } else {
+            self.anchor = self.cursor;
+        }
+
+        self.get_config().set_invalidate(true);
+    }
+
+    /// Returns the character index closest to the screen X coordinate, based on the character
+    /// offsets that were calculated during the last draw.  As the text may have been edited since,
+    /// the index is clamped to the length of the text.
+    fn char_index_at(&mut self, x: i32) -> usize {
+        let text_left = self.text_left();
+        let text_x = x - self.get_config().to_x(text_left) + self.scroll_x;
+
+        self.char_offsets
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, offset)| (*offset - text_x).abs())
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+            .min(self.text.chars().count())
+    }
+
+    fn text_left(&mut self) -> i32 {
+        self.try_get(KEY_BORDER_WIDTH).unwrap_or_default() + TEXT_PADDING
+    }
+}
+
+/// This is the `Widget` implementation of the `TextBoxWidget`.
+impl Widget for TextBoxWidget {
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the `TextBoxWidget` contents: the background, border, selection highlight, text,
+    /// IME pre-edit text, and the cursor when focused.  Returns an error if the font could not be
+    /// loaded, or the text could not be rendered.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
+        let base_color = self
+            .try_get(KEY_COLOR_BASE)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let border_color = self
+            .try_get(KEY_COLOR_BORDER)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let text_color = self
+            .try_get(KEY_COLOR_TEXT)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let selected_color = self
+            .try_get(KEY_COLOR_SELECTED)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let border_width = self.try_get(KEY_BORDER_WIDTH).unwrap_or_default();
+        let size = self.try_get(KEY_SIZE).unwrap_or_default();
+        let text_left = self.text_left();
+        let inner_w = size[0] as i32 - (text_left * 2);
+        let inner_h = size[1] as i32 - (text_left * 2);
+
+        c.set_draw_color(base_color);
+        c.fill_rect(self.get_drawing_area())
+            .map_err(PushrodError::Render)?;
+
+        c.set_draw_color(border_color);
+
+        for border in 0..border_width {
+            c.draw_rect(Rect::new(
+                self.config.to_x(border),
+                self.config.to_y(border),
+                size[0] - (border as u32 * 2),
+                size[1] - (border as u32 * 2),
+            ))
+            .map_err(PushrodError::Render)?;
+        }
+
+        if inner_w <= 0 || inner_h <= 0 {
+            return Ok(());
+        }
+
+        let font = load_font(c, &self.font_name, self.font_size as u16, FontStyle::NORMAL)?;
+
+        // The pre-edit text of an IME composition is displayed at the cursor position, and is
+        // not part of the text until it is committed.
+        let cursor_byte = self.byte_index(self.cursor);
+        let display_text = format!(
+            "{}{}{}",
+            &self.text[..cursor_byte],
+            self.composition,
+            &self.text[cursor_byte..]
+        );
+        let text_width = |s: &str| -> i32 {
+            if s.is_empty() {
+                0
+            } else {
+                font.size_of(s).map(|(w, _)| w as i32).unwrap_or(0)
+            }
+        };
+
+        self.char_offsets = (0..=self.text.chars().count())
+            .map(|i| text_width(&self.text[..self.byte_index(i)]))
+            .collect();
+
+        let composition_prefix: String = self
+            .composition
+            .chars()
+            .take(self.composition_cursor)
+            .collect();
+        let caret_x = self.char_offsets[self.cursor] + text_width(&composition_prefix);
+
+        // Scroll horizontally so that the cursor is always visible.
+        if caret_x - self.scroll_x > inner_w {
+            self.scroll_x = caret_x - inner_w;
+        } else if caret_x < self.scroll_x {
+            self.scroll_x = caret_x;
+        }
+
+        let origin_x = self.config.to_x(text_left) - self.scroll_x;
+        let inner_rect = Rect::new(
+            self.config.to_x(text_left),
+            self.config.to_y(text_left),
+            inner_w as u32,
+            inner_h as u32,
+        );
+        let previous_clip = c.clip_rect();
+
+        c.set_clip_rect(Some(inner_rect));
+
+        // The clip is restored whether or not drawing the contents succeeds.
+        let result = (|| -> Result<(), PushrodError> {
+            if self.has_selection() && self.composition.is_empty() {
+                let (start, end) = self.get_selection();
+                let start_x = self.char_offsets[start];
+                let end_x = self.char_offsets[end];
+
+                c.set_draw_color(selected_color);
+                c.fill_rect(Rect::new(
+                    origin_x + start_x,
+                    inner_rect.y(),
+                    (end_x - start_x) as u32,
+                    inner_h as u32,
+                ))
+                .map_err(PushrodError::Render)?;
+            }
+
+            if !display_text.is_empty() {
+                let surface = font
+                    .render(&display_text)
+                    .blended(text_color)
+                    .map_err(|e| PushrodError::Render(e.to_string()))?;
+                let text_y = inner_rect.y() + (inner_h - surface.height() as i32) / 2;
+
+                c.copy_surface(
+                    &surface,
+                    None,
+                    Rect::new(origin_x, text_y, surface.width(), surface.height()),
+                )
+                .map_err(PushrodError::Render)?;
+            }
+
+            if !self.composition.is_empty() {
+                let start_x = origin_x + self.char_offsets[self.cursor];
+                let end_x = start_x + text_width(&self.composition);
+                let underline_y = inner_rect.bottom() - 1;
+
+                c.set_draw_color(text_color);
+                c.draw_line(
+                    Point::new(start_x, underline_y),
+                    Point::new(end_x, underline_y),
+                )
+                .map_err(PushrodError::Render)?;
+            }
+
+            if self.focused {
+                let x = origin_x + caret_x;
+
+                c.set_draw_color(text_color);
+                c.draw_line(
+                    Point::new(x, inner_rect.y()),
+                    Point::new(x, inner_rect.bottom() - 1),
+                )
+                .map_err(PushrodError::Render)?;
+            }
+
+            Ok(())
+        })();
+
+        c.set_clip_rect(previous_clip);
+
+        result
+    }
+
+    /// Tracks the mouse position, so that clicking places the cursor, and dragging extends the
+    /// selection.
+    fn mouse_moved(&mut self, _widgets: &[WidgetContainer], _points: Points) {
+        self.mouse_x = _points[0];
+
+        if self.selecting {
+            let position = self.char_index_at(self.mouse_x);
+
+            self.move_cursor(position, true);
+        }
+
+        self.mouse_moved_callback(_widgets, _points);
+    }
+
+    /// Places the cursor at the clicked position.  Holding the mouse button down and dragging
+    /// selects text.
+    fn button_clicked(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _button: u8,
+        _clicks: u8,
+        _state: bool,
+    ) {
+        if _button == 1 {
+            if _state {
+                let position = self.char_index_at(self.mouse_x);
+
+                if _clicks > 1 {
+                    let len = self.text.chars().count();
+
+                    self.set_selection(0, len);
+                } else {
+                    self.move_cursor(position, false);
+                    self.selecting = true;
+                }
+            } else {
+                self.selecting = false;
+            }
+        }
+
+        self.button_clicked_callback(_widgets, _button, _clicks, _state);
+    }
+
+    /// Handles cursor movement, selection, deletion, and submission keys.  Keys are ignored while
+    /// an IME composition is in progress, as the input method handles them.
+    fn key_pressed(&mut self, _widgets: &[WidgetContainer], _keycode: Keycode, _keymod: Mod) {
+        if self.composition.is_empty() {
+            let shift = _keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+            let ctrl = _keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+            let len = self.text.chars().count();
+
+            match _keycode {
+                Keycode::Left => {
+                    let (start, _) = self.get_selection();
+
+                    if self.has_selection() && !shift {
+                        self.move_cursor(start, false);
+                    } else if self.cursor > 0 {
+                        self.move_cursor(self.cursor - 1, shift);
+                    }
+                }
+
+                Keycode::Right => {
+                    let (_, end) = self.get_selection();
+
+                    if self.has_selection() && !shift {
+                        self.move_cursor(end, false);
+                    } else if self.cursor < len {
+                        self.move_cursor(self.cursor + 1, shift);
+                    }
+                }
+
+                Keycode::Home => self.move_cursor(0, shift),
+
+                Keycode::End => self.move_cursor(len, shift),
+
+                Keycode::A if ctrl => self.set_selection(0, len),
+
+                Keycode::Backspace => {
+                    if self.delete_selection() {
+                        self.text_changed(_widgets);
+                    } else if self.cursor > 0 {
+                        let byte_start = self.byte_index(self.cursor - 1);
+                        let byte_end = self.byte_index(self.cursor);
+
+                        self.text.replace_range(byte_start..byte_end, "");
+                        self.cursor -= 1;
+                        self.anchor = self.cursor;
+                        self.text_changed(_widgets);
+                    }
+                }
+
+                Keycode::Delete => {
+                    if self.delete_selection() {
+                        self.text_changed(_widgets);
+                    } else if self.cursor < len {
+                        let byte_start = self.byte_index(self.cursor);
+                        let byte_end = self.byte_index(self.cursor + 1);
+
+                        self.text.replace_range(byte_start..byte_end, "");
+                        self.text_changed(_widgets);
+                    }
+                }
+
+                Keycode::Return | Keycode::KpEnter => self.call_submit_callback(_widgets),
+
+                _ => (),
+            }
+        }
+
+        self.key_pressed_callback(_widgets, _keycode, _keymod);
+    }
+
+    /// Inserts committed text at the cursor, replacing any selected text.
+    fn text_input(&mut self, _widgets: &[WidgetContainer], _text: String) {
+        self.delete_selection();
+
+        let byte_index = self.byte_index(self.cursor);
+
+        self.text.insert_str(byte_index, &_text);
+        self.cursor += _text.chars().count();
+        self.anchor = self.cursor;
+        self.composition.clear();
+        self.composition_cursor = 0;
+        self.text_changed(_widgets);
+    }
+
+    /// Stores the in-progress IME composition, which is drawn at the cursor until committed.
+    fn text_edited(
+        &mut self,
+        _widgets: &[WidgetContainer],
+        _text: String,
+        _start: i32,
+        _length: i32,
+    ) {
+        self.composition_cursor = (_start.max(0) as usize).min(_text.chars().count());
+        self.composition = _text;
+        self.get_config().set_invalidate(true);
+    }
+
+    /// Displays the cursor when focus is gained.
+    fn focus_gained(&mut self, _widgets: &[WidgetContainer]) {
+        self.focused = true;
+        self.get_config().set_invalidate(true);
+        self.focus_gained_callback(_widgets);
+    }
+
+    /// Hides the cursor, and discards any uncommitted IME composition when focus is lost.
+    fn focus_lost(&mut self, _widgets: &[WidgetContainer]) {
+        self.focused = false;
+        self.selecting = false;
+        self.composition.clear();
+        self.get_config().set_invalidate(true);
+        self.focus_lost_callback(_widgets);
+    }
+
+    /// Monitors for changes in the text, colors, or font size.  Setting `CONFIG_TEXT` replaces
+    /// the text, and moves the cursor to the end of the new text.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match _k {
+            CONFIG_COLOR_TEXT | CONFIG_COLOR_BASE | CONFIG_COLOR_BORDER | CONFIG_COLOR_SELECTED => {
+                self.get_config().set_invalidate(true)
+            }
+
+            CONFIG_FONT_SIZE => {
+                if let Config::Numeric(size) = _v {
+                    self.font_size = size;
+                    self.get_config().set_invalidate(true);
+                }
+            }
+
+            CONFIG_TEXT => {
+                if let Config::Text(text) = _v {
+                    self.cursor = text.chars().count();
+                    self.anchor = self.cursor;
+                    self.scroll_x = 0;
+                    self.text = text;
+                    self.get_config().set_invalidate(true);
+                }
+            }
+
+            _ => (),
+        };
+    }
+
+    default_widget_properties!();
+    default_widget_callbacks!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::resources::DEFAULT_FONT;
+
+    #[test]
+    fn click_after_delete_keeps_cursor_in_text() {
+        let mut text_box = TextBoxWidget::new(
+            String::from(DEFAULT_FONT),
+            16,
+            String::from("hello"),
+            0,
+            0,
+            200,
+            30,
+        );
+
+        // Character offsets as they were calculated when "hello" was last drawn.
+        text_box.char_offsets = vec![0, 10, 20, 30, 40, 50];
+        text_box.focus_gained(&[]);
+
+        for _ in 0..3 {
+            text_box.key_pressed(&[], Keycode::Backspace, Mod::NOMOD);
+        }
+
+        assert_eq!(text_box.text, "he");
+
+        // Clicking past the end of the stale offsets must not place the cursor past the text.
+        text_box.mouse_moved(&[], vec![190, 10]);
+        text_box.button_clicked(&[], 1, 1, true);
+
+        assert_eq!(text_box.get_cursor(), 2);
+        assert_eq!(text_box.get_selection(), (2, 2));
+
+        text_box.button_clicked(&[], 1, 1, false);
+
+        // Dragging a selection past the end of the text is clamped the same way.
+        text_box.mouse_moved(&[], vec![0, 10]);
+        text_box.button_clicked(&[], 1, 1, true);
+        text_box.mouse_moved(&[], vec![190, 10]);
+
+        assert_eq!(text_box.get_selection(), (0, 2));
+    }
+}
@@ -19,13 +19,15 @@ use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
 use crate::render::Points;
 
-use sdl2::render::{Canvas, TextureQuery};
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::load_font;
+
 use sdl2::ttf::FontStyle;
-use sdl2::video::Window;
 
+use sdl2::pixels::Color;
 use sdl2::rect::Rect;
 use std::collections::HashMap;
-use std::path::Path;
 
 /// This enum is used by the `TextWidget`, which controls the justification of the text being
 /// rendered within the bounds of the `Widget`.
@@ -40,8 +42,8 @@ pub enum TextJustify {
     Right,
 }
 
-/// This is the storage object for the `TextWidget`.  It stores the config, properties, callback registry,
-/// the font name, style, size, justification, and text message.
+/// This is the storage object for the `TextWidget`.  It stores the config, properties, callback
+/// registry, the font name, style, size, justification, and text message.
 pub struct TextWidget {
     config: WidgetConfig,
     system_properties: HashMap<i32, String>,
@@ -56,9 +58,12 @@ pub struct TextWidget {
 /// Creates a new `TextWidget`, which draws a unit of text on the screen, given the specified font,
 /// size, justification, and layout coordinates.
 impl TextWidget {
-    /// Creates a new `TextWidget` object.  Requires the name of the font (the path to the font file),
-    /// the style of font (`sdl2::ttf::FontStyle`), the size in pixels of the font, the `TextJustify`
-    /// layout of the font, the message to display, and the x, y, w, h coordinates of the text.
+    /// Creates a new `TextWidget` object.  Requires the name of the font (a font registered in the
+    /// `ResourceCache`, such as `DEFAULT_FONT`, or the path to a font file), the style of font
+    /// (`sdl2::ttf::FontStyle`), the size in pixels of the font, the `TextJustify` layout of the
+    /// font, the message to display, and the x, y, w, h coordinates of the text.
+    /// Use `set_render_cached` on the `WidgetConfig` to have the text only rendered again when it
+    /// changes.
     pub fn new(
         font_name: String,
         font_style: FontStyle,
@@ -83,38 +88,48 @@ impl TextWidget {
     }
 }
 
-/// This is the `Widget` implementation of the `TextWidget`.  Text is rendered onto a 3D texture, then
-/// copied to the canvas after rendering.  It uses blended mode texture mapping, which may be slow (as
-/// described by the SDL2 documentation), so this might change later to use 8 bit color mapping.
+/// This is the `Widget` implementation of the `TextWidget`.  Text is rendered onto a 3D texture,
+/// then copied to the canvas after rendering.  It uses blended mode texture mapping, which may be
+/// slow (as described by the SDL2 documentation), so this might change later to use 8 bit color
+/// mapping.
 impl Widget for TextWidget {
-    fn draw(&mut self, c: &mut Canvas<Window>) {
-        let base_color = self.get_color(CONFIG_COLOR_BASE);
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the text, returning an error if the font could not be loaded, or the text could not
+    /// be rendered.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
+        let base_color = self
+            .try_get(KEY_COLOR_BASE)
+            .unwrap_or(Color::RGB(255, 255, 255));
+        let border_width = self.try_get(KEY_BORDER_WIDTH).unwrap_or_default();
         let text_max_width =
-            self.get_size(CONFIG_SIZE)[0] - ((self.get_numeric(CONFIG_BORDER_WIDTH) * 2) as u32);
+            self.try_get(KEY_SIZE).unwrap_or_default()[0] - (border_width * 2) as u32;
 
-        let ttf_context = sdl2::ttf::init().map_err(|e| e.to_string()).unwrap();
-        let texture_creator = c.texture_creator();
-        let mut font = ttf_context
-            .load_font(Path::new(&self.font_name), self.font_size as u16)
-            .unwrap();
-        let font_color = self.get_color(CONFIG_COLOR_TEXT);
+        c.set_draw_color(base_color);
+        c.fill_rect(self.get_drawing_area())
+            .map_err(PushrodError::Render)?;
+
+        // SDL_ttf refuses to render an empty string, so there is nothing more to draw.
+        if self.msg.is_empty() {
+            return Ok(());
+        }
 
-        font.set_style(self.font_style);
+        let font = load_font(c, &self.font_name, self.font_size as u16, self.font_style)?;
+        let font_color = self
+            .try_get(KEY_COLOR_TEXT)
+            .unwrap_or(Color::RGB(255, 255, 255));
 
         let surface = font
             .render(&self.msg)
             .blended_wrapped(font_color, text_max_width)
-            .map_err(|e| e.to_string())
-            .unwrap();
-        let texture = texture_creator
-            .create_texture_from_surface(&surface)
-            .map_err(|e| e.to_string())
-            .unwrap();
-
-        let TextureQuery { width, height, .. } = texture.query();
+            .map_err(|e| PushrodError::Render(e.to_string()))?;
+        let width = surface.width();
+        let height = surface.height();
 
         let texture_y = self.get_config().to_y(0);
-        let widget_w = self.get_size(CONFIG_SIZE)[0] as i32;
+        let widget_w = self.try_get(KEY_SIZE).unwrap_or_default()[0] as i32;
         let texture_x = match self.justification {
             TextJustify::Left => self.get_config().to_x(0),
 
@@ -123,18 +138,15 @@ impl Widget for TextWidget {
             TextJustify::Center => self.get_config().to_x((widget_w - width as i32) / 2),
         };
 
-        c.set_draw_color(base_color);
-        c.fill_rect(self.get_drawing_area()).unwrap();
-
-        c.copy(
-            &texture,
+        c.copy_surface(
+            &surface,
             None,
             Rect::new(texture_x, texture_y, width, height),
         )
-        .unwrap();
+        .map_err(PushrodError::Render)
     }
 
-    /// Monitors for changes in the text, color changes, or font sizes.
+    /// Monitors for changes in the text, color changes, fonts, or font sizes.
     fn on_config_changed(&mut self, _k: u8, _v: Config) {
         match _k {
             CONFIG_COLOR_TEXT => self.get_config().set_invalidate(true),
@@ -146,6 +158,14 @@ impl Widget for TextWidget {
                 }
                 _ => (),
             },
+            CONFIG_FONT => {
+                if let Config::Font(font) = _v {
+                    self.font_name = font.name;
+                    self.font_size = i32::from(font.size);
+                    self.font_style = font.style;
+                    self.get_config().set_invalidate(true);
+                }
+            }
             CONFIG_TEXT => match _v {
                 Config::Text(text) => {
                     self.msg = text.clone();
@@ -110,5 +110,10 @@ impl Widget for TimerWidget {
         }
     }
 
+    /// The `TimerWidget` needs to receive `tick` calls while the timer is enabled.
+    fn needs_tick(&mut self) -> bool {
+        self.enabled
+    }
+
     default_widget_properties!();
 }
@@ -19,8 +19,9 @@ use crate::render::widget_cache::WidgetContainer;
 use crate::render::widget_config::*;
 use crate::render::Points;
 
-use sdl2::render::Canvas;
-use sdl2::video::Window;
+use crate::render::canvas::RenderCanvas;
+use crate::render::error::PushrodError;
+use crate::render::resources::DEFAULT_FONT;
 
 use crate::widgets::text_widget::{TextJustify, TextWidget};
 use sdl2::pixels::Color;
@@ -61,7 +62,7 @@ impl ToggleButtonWidget {
     ) -> Self {
         let mut base_widget = BaseWidget::new(x, y, w, h);
         let mut text_widget = TextWidget::new(
-            String::from("assets/OpenSans-Regular.ttf"),
+            String::from(DEFAULT_FONT),
             sdl2::ttf::FontStyle::NORMAL,
             font_size,
             TextJustify::Center,
@@ -164,12 +165,18 @@ impl ToggleButtonWidget {
 
 /// This is the `Widget` implementation of the `ToggleButtonWidget`.
 impl Widget for ToggleButtonWidget {
-    /// Draws the `ToggleButtonWidget` contents.
-    fn draw(&mut self, c: &mut Canvas<Window>) {
+    fn draw(&mut self, c: &mut dyn RenderCanvas) {
+        draw_with_placeholder(self, c);
+    }
+
+    /// Draws the `ToggleButtonWidget` contents, returning an error if its text could not be drawn.
+    fn try_draw(&mut self, c: &mut dyn RenderCanvas) -> Result<(), PushrodError> {
         // Paint the base widget first.  Forcing a draw() call here will ignore invalidation.
         // Invalidation is controlled by the top level widget (this box).
-        self.base_widget.draw(c);
-        self.text_widget.draw(c);
+        self.base_widget.try_draw(c)?;
+        self.text_widget.try_draw(c)?;
+
+        Ok(())
     }
 
     /// When a mouse enters the bounds of the `Widget`, this function is triggered.
@@ -218,6 +225,22 @@ impl Widget for ToggleButtonWidget {
         self.button_clicked_callback(_widgets, _button, _clicks, _state);
     }
 
+    /// Updates the selected state when `CONFIG_SELECTED_STATE` is changed, and passes a new
+    /// `CONFIG_FONT` on to the text of the `Widget`.
+    fn on_config_changed(&mut self, _k: u8, _v: Config) {
+        match (_k, _v) {
+            (CONFIG_SELECTED_STATE, Config::Toggle(selected)) => {
+                self.selected = selected;
+                self.draw_unhovered();
+            }
+            (CONFIG_FONT, Config::Font(font)) => {
+                self.text_widget.set_font(CONFIG_FONT, font);
+                self.get_config().set_invalidate(true);
+            }
+            _ => (),
+        }
+    }
+
     default_widget_properties!();
     default_widget_callbacks!();
 }